@@ -35,14 +35,29 @@
 //! You can programmatically control the video (e.g., seek, pause, loop, grab thumbnails) by accessing various methods on [`Video`].
 
 mod pipeline;
+mod renderer;
+mod seek_bar;
+mod thumbnails;
 mod video;
 mod video_player;
 
+#[cfg(test)]
+mod tests;
+
 use gstreamer as gst;
 use thiserror::Error;
 
+pub use renderer::VideoRenderer;
+pub use seek_bar::SeekBar;
+pub use thumbnails::Thumbnails;
 pub use video::Position;
+pub use video::RecoveryPolicy;
+pub use video::Stats;
+pub use video::Track;
+pub use video::TrackKind;
 pub use video::Video;
+pub use video::VideoEvent;
+pub use video::VideoOptions;
 pub use video_player::VideoPlayer;
 
 #[derive(Debug, Error)]
@@ -73,4 +88,10 @@ pub enum Error {
     Lock,
     #[error("invalid framerate: {0}")]
     Framerate(f64),
+    #[error("timed out waiting for the media")]
+    Timeout,
+    #[error("no stream with index {0}")]
+    Track(usize),
+    #[error("failed to step through frames")]
+    Step,
 }
@@ -22,6 +22,7 @@ struct VideoEntry {
     instances: wgpu::Buffer,
     bg0: wgpu::BindGroup,
     alive: Arc<AtomicBool>,
+    size: (u32, u32),
 
     prepare_index: AtomicUsize,
     render_index: AtomicUsize,
@@ -151,6 +152,24 @@ impl VideoPipeline {
         (width, height): (u32, u32),
         frame: &[u8],
     ) {
+        // a frame from before a resolution change can still be in flight
+        if frame.len() < (width * height + width * height / 2) as usize {
+            return;
+        }
+
+        // the resolution can change mid-stream (e.g., playlists), so recreate the textures
+        if self
+            .videos
+            .get(&video_id)
+            .is_some_and(|video| video.size != (width, height))
+        {
+            if let Some(video) = self.videos.remove(&video_id) {
+                video.texture_y.destroy();
+                video.texture_uv.destroy();
+                video.instances.destroy();
+            }
+        }
+
         if let Entry::Vacant(entry) = self.videos.entry(video_id) {
             let texture_y = device.create_texture(&wgpu::TextureDescriptor {
                 label: Some("iced_video_player texture"),
@@ -246,6 +265,7 @@ impl VideoPipeline {
                 instances,
                 bg0: bind_group,
                 alive: Arc::clone(alive),
+                size: (width, height),
 
                 prepare_index: AtomicUsize::new(0),
                 render_index: AtomicUsize::new(0),
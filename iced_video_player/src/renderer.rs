@@ -0,0 +1,45 @@
+use iced::advanced::image;
+use iced_renderer::fallback;
+use iced_wgpu::primitive::Renderer as PrimitiveRenderer;
+
+/// A renderer which can draw a [`VideoPlayer`](crate::VideoPlayer).
+///
+/// Frames are drawn as a custom wgpu primitive, with the color space converted on the GPU,
+/// whenever the renderer supports it. Otherwise (e.g., iced fell back to tiny-skia because
+/// no working GPU driver was found) frames are converted on the CPU and drawn as images.
+pub trait VideoRenderer: image::Renderer<Handle = image::Handle> {
+    /// Draws a custom wgpu primitive, returning `false` if the active backend cannot draw it.
+    fn try_draw_primitive(
+        &mut self,
+        bounds: iced::Rectangle,
+        primitive: impl iced_wgpu::Primitive,
+    ) -> bool;
+}
+
+impl VideoRenderer for iced_wgpu::Renderer {
+    fn try_draw_primitive(
+        &mut self,
+        bounds: iced::Rectangle,
+        primitive: impl iced_wgpu::Primitive,
+    ) -> bool {
+        self.draw_primitive(bounds, primitive);
+        true
+    }
+}
+
+impl<A, B> VideoRenderer for fallback::Renderer<A, B>
+where
+    A: VideoRenderer,
+    B: image::Renderer<Handle = image::Handle>,
+{
+    fn try_draw_primitive(
+        &mut self,
+        bounds: iced::Rectangle,
+        primitive: impl iced_wgpu::Primitive,
+    ) -> bool {
+        match self {
+            fallback::Renderer::Primary(renderer) => renderer.try_draw_primitive(bounds, primitive),
+            fallback::Renderer::Secondary(_) => false,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::Video;
+use iced::{
+    advanced::text,
+    widget::{row, slider, Text},
+    Alignment, Element, Length,
+};
+use std::time::Duration;
+
+/// A seek bar for a [`Video`]: the current position, a slider over the media duration, and the duration.
+///
+/// While the slider is dragged, it follows [`Video::scrub_position`] rather than the actual
+/// position, so it doesn't jump back and forth between keyframes.
+///
+/// Example:
+/// ```rust,ignore
+/// SeekBar::new(&self.video, Message::Scrub).on_release(Message::EndScrub)
+/// ```
+/// with `Message::Scrub(position)` calling [`Video::scrub`] and `Message::EndScrub` calling [`Video::end_scrub`].
+pub struct SeekBar<'a, Message> {
+    video: &'a Video,
+    on_scrub: Box<dyn Fn(Duration) -> Message + 'a>,
+    on_release: Option<Message>,
+    width: Length,
+}
+
+impl<'a, Message> SeekBar<'a, Message> {
+    /// Creates a new seek bar for `video`, sending `on_scrub` with the position the slider was dragged to.
+    pub fn new(video: &'a Video, on_scrub: impl Fn(Duration) -> Message + 'a) -> Self {
+        SeekBar {
+            video,
+            on_scrub: Box::new(on_scrub),
+            on_release: None,
+            width: Length::Fill,
+        }
+    }
+
+    /// Message to send when the slider is released.
+    pub fn on_release(self, on_release: Message) -> Self {
+        SeekBar {
+            on_release: Some(on_release),
+            ..self
+        }
+    }
+
+    /// Set the width of the seek bar.
+    pub fn width(self, width: impl Into<Length>) -> Self {
+        SeekBar {
+            width: width.into(),
+            ..self
+        }
+    }
+}
+
+/// Formats a position as `m:ss`, or `h:mm:ss` for an hour or longer.
+pub(crate) fn format_time(time: Duration) -> String {
+    let seconds = time.as_secs();
+    if seconds >= 3600 {
+        format!(
+            "{}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    } else {
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<SeekBar<'a, Message>> for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Theme: 'a + slider::Catalog + iced::widget::text::Catalog,
+    Renderer: 'a + text::Renderer,
+{
+    fn from(seek_bar: SeekBar<'a, Message>) -> Self {
+        let duration = seek_bar.video.duration();
+        let position = seek_bar
+            .video
+            .scrub_position()
+            .unwrap_or_else(|| seek_bar.video.position())
+            .min(duration);
+
+        let on_scrub = seek_bar.on_scrub;
+        let mut slider = slider(
+            0.0..=duration.as_secs_f64(),
+            position.as_secs_f64(),
+            move |seconds| on_scrub(Duration::from_secs_f64(seconds)),
+        )
+        .step(0.01);
+        if let Some(on_release) = seek_bar.on_release {
+            slider = slider.on_release(on_release);
+        }
+
+        row![
+            Text::new(format_time(position)),
+            slider,
+            Text::new(format_time(duration)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .width(seek_bar.width)
+        .into()
+    }
+}
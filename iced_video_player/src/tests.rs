@@ -0,0 +1,670 @@
+//! Headless rendering harness: drives [`Video`] from `videotestsrc` pipelines and renders
+//! [`VideoPrimitive`] into an offscreen texture on a software wgpu adapter (e.g., lavapipe),
+//! so the render path can be checked on machines without a GPU.
+//!
+//! Network loading is checked against a local HTTP server serving an encoded `videotestsrc` clip and an HLS playlist of it.
+
+use crate::pipeline::VideoPrimitive;
+use crate::video_player::drawing_bounds;
+use crate::Video;
+use gstreamer as gst;
+use gstreamer_app as gst_app;
+use gstreamer_app::prelude::*;
+use iced::wgpu;
+use iced::widget::shader::{Primitive, Storage, Viewport};
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const TARGET_SIZE: u32 = 64;
+const TOLERANCE: u8 = 12;
+
+const RED: [u8; 3] = [255, 0, 0];
+const BLACK: [u8; 3] = [0, 0, 0];
+
+/// Creates a video from a `videotestsrc` pattern (e.g., `pattern=solid-color foreground-color=0xffff0000`)
+/// and waits for its first frame.
+fn test_video(pattern: &str, width: u32, height: u32) -> Video {
+    gst::init().expect("init gstreamer");
+
+    let pipeline = format!("videotestsrc {pattern} ! video/x-raw,width={width},height={height},framerate=30/1 ! videoconvert ! appsink name=iced_video drop=true caps=video/x-raw,format=NV12,pixel-aspect-ratio=1/1");
+    let pipeline = gst::parse::launch(&pipeline)
+        .expect("parse test pipeline")
+        .downcast::<gst::Pipeline>()
+        .expect("test pipeline is a pipeline");
+    let video_sink = pipeline
+        .by_name("iced_video")
+        .expect("test pipeline has an appsink")
+        .downcast::<gst_app::AppSink>()
+        .expect("iced_video is an appsink");
+
+    let video = Video::from_gst_pipeline(pipeline, video_sink, None).expect("create video");
+
+    let start = Instant::now();
+    while !video.read().upload_frame.load(Ordering::SeqCst) {
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "timed out waiting for the first frame"
+        );
+        std::thread::sleep(Duration::from_millis(5));
+    }
+
+    video
+}
+
+fn primitive(video: &Video) -> VideoPrimitive {
+    let inner = video.read();
+    VideoPrimitive::new(
+        inner.id,
+        Arc::clone(&inner.alive),
+        Arc::clone(&inner.frame),
+        (inner.width as _, inner.height as _),
+        true,
+    )
+}
+
+struct Headless {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+}
+
+impl Headless {
+    /// Panics if no software adapter is available, render tests are opt-in through `--ignored` for that reason.
+    fn new() -> Self {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::LowPower,
+            force_fallback_adapter: true,
+            compatible_surface: None,
+        }))
+        .expect("no software wgpu adapter available, install lavapipe or another fallback driver");
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))
+                .expect("request a device from the software adapter");
+        Headless { device, queue }
+    }
+
+    /// Renders `primitive` into a black `TARGET_SIZE` square and reads back its RGBA pixels.
+    fn render(
+        &self,
+        primitive: &VideoPrimitive,
+        bounds: iced::Rectangle,
+        clip: iced::Rectangle<u32>,
+    ) -> Vec<u8> {
+        let format = wgpu::TextureFormat::Rgba8UnormSrgb;
+        let extent = wgpu::Extent3d {
+            width: TARGET_SIZE,
+            height: TARGET_SIZE,
+            depth_or_array_layers: 1,
+        };
+
+        let target = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("iced_video_player test target"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut storage = Storage::default();
+        let viewport =
+            Viewport::with_physical_size(iced::Size::new(TARGET_SIZE, TARGET_SIZE), 1.0);
+        primitive.prepare(
+            &self.device,
+            &self.queue,
+            format,
+            &mut storage,
+            &bounds,
+            &viewport,
+        );
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("iced_video_player test clear pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        primitive.render(&mut encoder, &storage, &view, &clip);
+
+        // rows of a texture copy must be aligned to 256 bytes
+        let bytes_per_row = (TARGET_SIZE * 4).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("iced_video_player test readback"),
+            size: (bytes_per_row * TARGET_SIZE) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &target,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &readback,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(TARGET_SIZE),
+                },
+            },
+            extent,
+        );
+
+        self.queue.submit([encoder.finish()]);
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |result| {
+            result.expect("map readback buffer")
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+
+        let mapped = slice.get_mapped_range();
+        mapped
+            .chunks(bytes_per_row as usize)
+            .flat_map(|row| &row[..(TARGET_SIZE * 4) as usize])
+            .copied()
+            .collect()
+    }
+}
+
+fn full_target() -> (iced::Rectangle, iced::Rectangle<u32>) {
+    (
+        iced::Rectangle::new(
+            iced::Point::ORIGIN,
+            iced::Size::new(TARGET_SIZE as f32, TARGET_SIZE as f32),
+        ),
+        iced::Rectangle {
+            x: 0,
+            y: 0,
+            width: TARGET_SIZE,
+            height: TARGET_SIZE,
+        },
+    )
+}
+
+fn pixel(pixels: &[u8], x: u32, y: u32) -> [u8; 3] {
+    let i = ((y * TARGET_SIZE + x) * 4) as usize;
+    [pixels[i], pixels[i + 1], pixels[i + 2]]
+}
+
+fn assert_color(actual: [u8; 3], expected: [u8; 3], at: (u32, u32)) {
+    let close = actual
+        .iter()
+        .zip(expected)
+        .all(|(a, e)| a.abs_diff(e) <= TOLERANCE);
+    assert!(close, "pixel at {at:?} is {actual:?}, expected {expected:?}");
+}
+
+#[test]
+#[ignore = "needs a software wgpu adapter, run with --ignored"]
+fn converts_nv12_to_rgb() {
+    let headless = Headless::new();
+
+    let colors = [
+        ("0xffff0000", RED),
+        ("0xff00ff00", [0, 255, 0]),
+        ("0xff0000ff", [0, 0, 255]),
+        ("0xffffffff", [255, 255, 255]),
+    ];
+
+    for (argb, expected) in colors {
+        let video = test_video(
+            &format!("pattern=solid-color foreground-color={argb}"),
+            TARGET_SIZE,
+            TARGET_SIZE,
+        );
+        let (bounds, clip) = full_target();
+        let pixels = headless.render(&primitive(&video), bounds, clip);
+
+        let center = TARGET_SIZE / 2;
+        assert_color(pixel(&pixels, center, center), expected, (center, center));
+    }
+}
+
+#[test]
+#[ignore = "needs a software wgpu adapter, run with --ignored"]
+fn contain_letterboxes_wide_video() {
+    let headless = Headless::new();
+
+    // a 2:1 video in a square target leaves a quarter of the height empty above and below
+    let video = test_video(
+        "pattern=solid-color foreground-color=0xffff0000",
+        TARGET_SIZE,
+        TARGET_SIZE / 2,
+    );
+    let (bounds, clip) = full_target();
+    let (width, height) = video.size();
+    let drawn = drawing_bounds(
+        iced::ContentFit::Contain,
+        iced::Size::new(width as f32, height as f32),
+        bounds,
+    );
+    let pixels = headless.render(&primitive(&video), drawn, clip);
+
+    let center = TARGET_SIZE / 2;
+    assert_color(pixel(&pixels, center, 4), BLACK, (center, 4));
+    assert_color(pixel(&pixels, center, center), RED, (center, center));
+    assert_color(
+        pixel(&pixels, center, TARGET_SIZE - 4),
+        BLACK,
+        (center, TARGET_SIZE - 4),
+    );
+}
+
+#[test]
+#[ignore = "needs a software wgpu adapter, run with --ignored"]
+fn scissor_clips_rendering() {
+    let headless = Headless::new();
+
+    let video = test_video(
+        "pattern=solid-color foreground-color=0xffff0000",
+        TARGET_SIZE,
+        TARGET_SIZE,
+    );
+    let (bounds, _) = full_target();
+    let left_half = iced::Rectangle {
+        x: 0,
+        y: 0,
+        width: TARGET_SIZE / 2,
+        height: TARGET_SIZE,
+    };
+    let pixels = headless.render(&primitive(&video), bounds, left_half);
+
+    let center = TARGET_SIZE / 2;
+    assert_color(pixel(&pixels, 4, center), RED, (4, center));
+    assert_color(
+        pixel(&pixels, TARGET_SIZE - 4, center),
+        BLACK,
+        (TARGET_SIZE - 4, center),
+    );
+}
+
+#[test]
+fn converts_nv12_to_rgba_on_cpu() {
+    use crate::video::nv12_to_rgba;
+
+    // a 4x2 frame in limited range BT.601: the left 2x2 block red, the right one blue
+    let y_plane = [81, 81, 41, 41, 81, 81, 41, 41];
+    let uv_plane = [90, 240, 240, 110];
+    let nv12 = [&y_plane[..], &uv_plane[..]].concat();
+
+    let rgba = nv12_to_rgba(&nv12, 4, 2).expect("convert a full frame");
+    assert_eq!(rgba.len(), 4 * 2 * 4);
+    for y in 0..2 {
+        for x in 0..4 {
+            let i = (y * 4 + x) * 4;
+            let expected = if x < 2 { RED } else { [0, 0, 255] };
+            assert_color([rgba[i], rgba[i + 1], rgba[i + 2]], expected, (x as u32, y as u32));
+            assert_eq!(rgba[i + 3], 255, "pixel at {:?} is not opaque", (x, y));
+        }
+    }
+
+    // a truncated buffer isn't a frame
+    assert_eq!(nv12_to_rgba(&nv12[..10], 4, 2), None);
+
+    // a 3x3 frame has a 2x2 chroma plane, the last row and column share the edge samples
+    let y_plane = [81; 9];
+    let uv_plane = [90, 240].repeat(4);
+    let nv12 = [&y_plane[..], &uv_plane[..]].concat();
+
+    let rgba = nv12_to_rgba(&nv12, 3, 3).expect("convert an odd sized frame");
+    assert_eq!(rgba.len(), 3 * 3 * 4);
+    for y in 0..3 {
+        for x in 0..3 {
+            let i = (y * 3 + x) * 4;
+            assert_color([rgba[i], rgba[i + 1], rgba[i + 2]], RED, (x as u32, y as u32));
+        }
+    }
+    assert_eq!(nv12_to_rgba(&nv12[..9 + 6], 3, 3), None);
+}
+
+/// Waits for the video sink to show `expected` after a step or seek, which complete asynchronously.
+fn wait_for_frame(video: &Video, expected: u64) {
+    let start = Instant::now();
+    loop {
+        let inner = video.read();
+        let frame = inner
+            .video_sink()
+            .expect("video has a sink")
+            .query_position::<gst::ClockTime>()
+            .map(|position| inner.frame_at(Duration::from_nanos(position.nseconds())));
+        drop(inner);
+        if frame == Some(expected) {
+            return;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "video shows frame {frame:?}, expected {expected}"
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn steps_by_video_frames() {
+    let mut video = test_video("pattern=ball", 64, 48);
+    video.set_paused(true);
+    video.seek(crate::Position::Frame(10), true).expect("seek");
+    wait_for_frame(&video, 10);
+
+    video.step_forward(3).expect("step forward");
+    wait_for_frame(&video, 13);
+
+    video.step_backward(2).expect("step backward");
+    wait_for_frame(&video, 11);
+}
+
+#[test]
+fn formats_seek_bar_time() {
+    use crate::seek_bar::format_time;
+
+    assert_eq!(format_time(Duration::ZERO), "0:00");
+    assert_eq!(format_time(Duration::from_millis(61_900)), "1:01");
+    assert_eq!(format_time(Duration::from_secs(3 * 3600 + 5 * 60 + 9)), "3:05:09");
+}
+
+#[test]
+fn content_fit_bounds() {
+    let video = iced::Size::new(160.0, 90.0);
+    let bounds = iced::Rectangle::new(iced::Point::new(10.0, 20.0), iced::Size::new(100.0, 100.0));
+
+    let contain = drawing_bounds(iced::ContentFit::Contain, video, bounds);
+    assert_eq!(contain.size(), iced::Size::new(100.0, 56.25));
+    assert_eq!(contain.center(), bounds.center());
+
+    let cover = drawing_bounds(iced::ContentFit::Cover, video, bounds);
+    assert_eq!(cover.height, 100.0);
+    assert!(cover.width > bounds.width);
+    assert_eq!(cover.center(), bounds.center());
+
+    let fill = drawing_bounds(iced::ContentFit::Fill, video, bounds);
+    assert_eq!(fill, bounds);
+
+    let none = drawing_bounds(iced::ContentFit::None, video, bounds);
+    assert_eq!(none.size(), video);
+}
+
+/// Encodes a short `videotestsrc` clip to use as fixture media, `None` if the encoder plugins are missing.
+fn fixture_media(width: u32, height: u32) -> Option<Vec<u8>> {
+    gst::init().expect("init gstreamer");
+    for element in ["jpegenc", "avimux", "souphttpsrc"] {
+        gst::ElementFactory::find(element)?;
+    }
+
+    // tests run in parallel, each needs its own file
+    static NEXT_FIXTURE: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    let path = std::env::temp_dir().join(format!(
+        "iced_video_player-fixture-{}-{}.avi",
+        std::process::id(),
+        NEXT_FIXTURE.fetch_add(1, Ordering::SeqCst)
+    ));
+    let pipeline = format!("videotestsrc num-buffers=60 ! video/x-raw,width={width},height={height},framerate=30/1 ! jpegenc ! avimux ! filesink location=\"{}\"", path.display());
+    let pipeline = gst::parse::launch(&pipeline).expect("parse fixture pipeline");
+    pipeline
+        .set_state(gst::State::Playing)
+        .expect("start fixture pipeline");
+    pipeline
+        .bus()
+        .expect("fixture pipeline has a bus")
+        .timed_pop_filtered(
+            gst::ClockTime::from_seconds(10),
+            &[gst::MessageType::Eos, gst::MessageType::Error],
+        )
+        .expect("fixture pipeline finished");
+    let _ = pipeline.set_state(gst::State::Null);
+
+    let media = std::fs::read(&path).expect("read fixture media");
+    let _ = std::fs::remove_file(path);
+
+    Some(media)
+}
+
+/// Encodes a short `videotestsrc` clip into an HLS playlist and its segments, keyed by file name.
+/// `None` if the encoder, muxer or HLS plugins are missing.
+fn fixture_hls(width: u32, height: u32) -> Option<HashMap<String, Vec<u8>>> {
+    gst::init().expect("init gstreamer");
+    for element in ["x264enc", "h264parse", "hlssink2", "souphttpsrc"] {
+        gst::ElementFactory::find(element)?;
+    }
+    // playbin picks whichever demuxer is installed
+    gst::ElementFactory::find("hlsdemux").or_else(|| gst::ElementFactory::find("hlsdemux2"))?;
+
+    let dir = std::env::temp_dir().join(format!("iced_video_player-hls-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("create hls fixture dir");
+    let pipeline = format!(
+        "videotestsrc num-buffers=90 ! video/x-raw,width={width},height={height},framerate=30/1 ! x264enc key-int-max=15 ! h264parse ! hlssink2 target-duration=1 playlist-length=0 location=\"{}\" playlist-location=\"{}\"",
+        dir.join("segment%05d.ts").display(),
+        dir.join("playlist.m3u8").display()
+    );
+    let pipeline = gst::parse::launch(&pipeline).expect("parse hls fixture pipeline");
+    pipeline
+        .set_state(gst::State::Playing)
+        .expect("start hls fixture pipeline");
+    pipeline
+        .bus()
+        .expect("hls fixture pipeline has a bus")
+        .timed_pop_filtered(
+            gst::ClockTime::from_seconds(10),
+            &[gst::MessageType::Eos, gst::MessageType::Error],
+        )
+        .expect("hls fixture pipeline finished");
+    let _ = pipeline.set_state(gst::State::Null);
+
+    let files = std::fs::read_dir(&dir)
+        .expect("read hls fixture dir")
+        .filter_map(Result::ok)
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let contents = std::fs::read(entry.path()).expect("read hls fixture file");
+            (name, contents)
+        })
+        .collect();
+    let _ = std::fs::remove_dir_all(dir);
+
+    Some(files)
+}
+
+/// Serves `media` over HTTP on a local port, with range requests so the source can seek.
+/// With `stall`, connections are accepted but never answered.
+fn serve(media: Vec<u8>, stall: bool) -> url::Url {
+    let base = serve_files(HashMap::from([("fixture.avi".to_owned(), media)]), stall);
+    base.join("fixture.avi").expect("test server url")
+}
+
+/// Serves `files` by name over HTTP on a local port and returns the server's base url, see [`serve`].
+fn serve_files(files: HashMap<String, Vec<u8>>, stall: bool) -> url::Url {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind test server");
+    let port = listener.local_addr().expect("test server address").port();
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            if stall {
+                std::thread::spawn(move || {
+                    std::thread::sleep(Duration::from_secs(30));
+                    drop(stream);
+                });
+                continue;
+            }
+
+            let mut name = String::new();
+            let mut start = 0;
+            let mut reader = BufReader::new(stream.try_clone().expect("clone test stream"));
+            let mut line = String::new();
+            while reader.read_line(&mut line).is_ok_and(|read| read > 2) {
+                // the request line, e.g. "GET /playlist.m3u8 HTTP/1.1"
+                if name.is_empty() && let Some(path) = line.split_whitespace().nth(1) {
+                    name = path.trim_start_matches('/').to_owned();
+                }
+                if let Some(range) = line.to_ascii_lowercase().strip_prefix("range: bytes=") {
+                    start = range
+                        .split('-')
+                        .next()
+                        .and_then(|start| start.trim().parse().ok())
+                        .unwrap_or(0);
+                }
+                line.clear();
+            }
+
+            let Some(media) = files.get(&name) else {
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                );
+                continue;
+            };
+            let content_type = match name.rsplit('.').next() {
+                Some("m3u8") => "application/vnd.apple.mpegurl",
+                Some("ts") => "video/mp2t",
+                _ => "video/x-msvideo",
+            };
+
+            let start = start.min(media.len());
+            let status = if start > 0 {
+                format!(
+                    "206 Partial Content\r\nContent-Range: bytes {start}-{}/{}",
+                    media.len() - 1,
+                    media.len()
+                )
+            } else {
+                "200 OK".to_owned()
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nAccept-Ranges: bytes\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                media.len() - start
+            );
+            let _ = stream.write_all(&media[start..]);
+        }
+    });
+
+    url::Url::parse(&format!("http://127.0.0.1:{port}/")).expect("test server url")
+}
+
+#[test]
+fn loads_http_media() {
+    let Some(media) = fixture_media(64, 48) else {
+        eprintln!("skipping: jpegenc, avimux or souphttpsrc not available");
+        return;
+    };
+    let uri = serve(media, false);
+
+    let video = pollster::block_on(Video::load(uri, crate::VideoOptions::default()))
+        .expect("load http media");
+    assert_eq!(video.size(), (64, 48));
+    assert!(!video.is_live());
+    assert!(video.duration() > Duration::from_secs(1));
+}
+
+#[test]
+fn loads_http_media_with_download() {
+    let Some(media) = fixture_media(64, 48) else {
+        eprintln!("skipping: jpegenc, avimux or souphttpsrc not available");
+        return;
+    };
+    let uri = serve(media, false);
+
+    let cache_dir = std::env::temp_dir().join(format!("iced_video_player-test-{}", std::process::id()));
+    std::fs::create_dir_all(&cache_dir).expect("create cache dir");
+    let options = crate::VideoOptions {
+        download: true,
+        cache_dir: Some(cache_dir.clone()),
+        ..Default::default()
+    };
+
+    let mut video = pollster::block_on(Video::load(uri, options)).expect("load http media");
+    assert_eq!(video.size(), (64, 48));
+    // the downloaded range can be seeked back into
+    video
+        .seek(Duration::from_millis(500), true)
+        .expect("seek downloaded media");
+
+    drop(video);
+    let _ = std::fs::remove_dir_all(cache_dir);
+}
+
+#[test]
+fn loads_hls_playlist() {
+    let Some(files) = fixture_hls(64, 48) else {
+        eprintln!("skipping: x264enc, hlssink2, hlsdemux or souphttpsrc not available");
+        return;
+    };
+    assert!(files.contains_key("playlist.m3u8"), "{:?}", files.keys());
+    let uri = serve_files(files, false)
+        .join("playlist.m3u8")
+        .expect("playlist url");
+
+    let video = pollster::block_on(Video::load(uri, crate::VideoOptions::default()))
+        .expect("load hls playlist");
+    assert_eq!(video.size(), (64, 48));
+    // the playlist ends with EXT-X-ENDLIST, so it is video on demand rather than live
+    assert!(!video.is_live());
+    assert!(video.duration() > Duration::from_secs(1));
+}
+
+#[test]
+fn thumbnails_seek_to_frames_by_time() {
+    let Some(media) = fixture_media(64, 48) else {
+        eprintln!("skipping: jpegenc, avimux or souphttpsrc not available");
+        return;
+    };
+    let path = std::env::temp_dir().join(format!("iced_video_player-thumbnails-{}.avi", std::process::id()));
+    std::fs::write(&path, media).expect("write fixture media");
+    let uri = url::Url::from_file_path(&path).expect("fixture url");
+
+    // the clip has 60 frames at 30 fps
+    let thumbnails = crate::Thumbnails::new(
+        &uri,
+        [crate::Position::Frame(30), crate::Position::Time(Duration::from_millis(500))],
+        std::num::NonZeroU8::new(2).unwrap(),
+        Duration::from_secs(5),
+    );
+    let thumbnails = pollster::block_on(thumbnails.collect_all());
+    let _ = std::fs::remove_file(path);
+
+    assert_eq!(thumbnails.expect("decode thumbnails").len(), 2);
+}
+
+#[test]
+fn times_out_on_stalled_server() {
+    if fixture_media(16, 16).is_none() {
+        eprintln!("skipping: jpegenc, avimux or souphttpsrc not available");
+        return;
+    }
+    let uri = serve(Vec::new(), true);
+
+    let options = crate::VideoOptions {
+        preroll_timeout: Duration::from_millis(500),
+        ..Default::default()
+    };
+    let start = Instant::now();
+    let result = pollster::block_on(Video::load(uri, options));
+
+    assert!(matches!(result, Err(crate::Error::Timeout)), "{result:?}");
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
@@ -0,0 +1,176 @@
+use crate::video::{yuv_to_rgba, Frame, Position};
+use crate::Error;
+use gstreamer as gst;
+use gstreamer_app as gst_app;
+use gstreamer_app::prelude::*;
+use iced::futures::channel::mpsc;
+use iced::futures::{Stream, StreamExt};
+use iced::widget::image as img;
+use std::num::NonZeroU8;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// A stream of thumbnails decoded on a worker thread, see [`Video::thumbnails`](crate::Video::thumbnails).
+///
+/// The worker uses its own decode pipeline, so generating thumbnails never disturbs playback.
+/// Each thumbnail is yielded as soon as it is decoded; dropping the stream (or calling
+/// [`Thumbnails::cancel`]) stops the worker after the current frame.
+#[derive(Debug)]
+pub struct Thumbnails {
+    receiver: mpsc::UnboundedReceiver<Result<img::Handle, Error>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Thumbnails {
+    /// Starts generating thumbnails of the media at `uri` for a set of positions, downscaled by a given factor.
+    ///
+    /// Every step (loading the media, seeking to each position) fails with [`Error::Timeout`]
+    /// if it takes longer than `timeout`.
+    pub fn new<I>(uri: &url::Url, positions: I, downscale: NonZeroU8, timeout: Duration) -> Self
+    where
+        I: IntoIterator<Item = Position>,
+    {
+        let (sender, receiver) = mpsc::unbounded();
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let uri = uri.to_string();
+        let positions: Vec<Position> = positions.into_iter().collect();
+        let downscale = u8::from(downscale) as u32;
+        let cancelled_ref = Arc::clone(&cancelled);
+
+        std::thread::spawn(move || {
+            if let Err(err) = (|| -> Result<(), Error> {
+                gst::init()?;
+
+                let pipeline = format!("playbin uri=\"{}\" flags=video video-sink=\"videoscale ! videoconvert ! appsink name=iced_thumbnail sync=false caps=video/x-raw,format=NV12,pixel-aspect-ratio=1/1\"", uri);
+                let pipeline = gst::parse::launch(pipeline.as_ref())?
+                    .downcast::<gst::Pipeline>()
+                    .map_err(|_| Error::Cast)?;
+
+                let result = decode(&pipeline, &positions, downscale, timeout, &cancelled_ref, &sender);
+                let _ = pipeline.set_state(gst::State::Null);
+                result
+            })() {
+                let _ = sender.unbounded_send(Err(err));
+            }
+        });
+
+        Thumbnails {
+            receiver,
+            cancelled,
+        }
+    }
+
+    /// A stream which yields a single error, e.g. when the media has no uri.
+    pub(crate) fn failed(err: Error) -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+        let _ = sender.unbounded_send(Err(err));
+        Thumbnails {
+            receiver,
+            cancelled: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    /// Stops generating thumbnails; the stream ends after the frame being decoded, if any.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Waits for every thumbnail, failing on the first error.
+    pub async fn collect_all(mut self) -> Result<Vec<img::Handle>, Error> {
+        let mut thumbnails = Vec::new();
+        while let Some(thumbnail) = self.next().await {
+            thumbnails.push(thumbnail?);
+        }
+        Ok(thumbnails)
+    }
+}
+
+impl Drop for Thumbnails {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+impl Stream for Thumbnails {
+    type Item = Result<img::Handle, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
+
+fn decode(
+    pipeline: &gst::Pipeline,
+    positions: &[Position],
+    downscale: u32,
+    timeout: Duration,
+    cancelled: &AtomicBool,
+    sender: &mpsc::UnboundedSender<Result<img::Handle, Error>>,
+) -> Result<(), Error> {
+    let video_sink: gst::Element = pipeline.property("video-sink");
+    let video_sink = video_sink
+        .downcast::<gst::Bin>()
+        .map_err(|_| Error::Cast)?
+        .by_name("iced_thumbnail")
+        .ok_or_else(|| Error::AppSink("iced_thumbnail".to_string()))?
+        .downcast::<gst_app::AppSink>()
+        .map_err(|_| Error::Cast)?;
+
+    let timeout = gst::ClockTime::from_nseconds(timeout.as_nanos() as _);
+
+    // paused is enough to decode a single (preroll) frame after every seek
+    pipeline.set_state(gst::State::Paused)?;
+    match pipeline.state(timeout) {
+        (Ok(gst::StateChangeSuccess::Async), _, _) => return Err(Error::Timeout),
+        (Err(err), _, _) => return Err(err.into()),
+        _ => {}
+    }
+
+    // frame positions are seeked to by time, like `Video::seek` does
+    let framerate = video_sink
+        .static_pad("sink")
+        .and_then(|pad| pad.current_caps())
+        .and_then(|caps| caps.structure(0)?.get::<gst::Fraction>("framerate").ok())
+        .map(|framerate| framerate.numer() as f64 / framerate.denom() as f64);
+
+    for &position in positions {
+        if cancelled.load(Ordering::SeqCst) {
+            break;
+        }
+
+        let time = match position {
+            Position::Time(time) => time,
+            Position::Frame(_) => match framerate {
+                Some(framerate) if framerate.is_normal() && framerate > 0.0 => {
+                    position.to_time(framerate)
+                }
+                framerate => return Err(Error::Framerate(framerate.unwrap_or(0.0))),
+            },
+        };
+        pipeline.seek_simple(
+            gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+            gst::ClockTime::from_nseconds(time.as_nanos() as _),
+        )?;
+
+        let frame = Frame(video_sink.try_pull_preroll(timeout).ok_or(Error::Timeout)?);
+        let (width, height) = frame.size().ok_or(Error::Caps)?;
+        let readable = frame.readable().ok_or(Error::Lock)?;
+
+        let thumbnail = img::Handle::from_rgba(
+            width as u32 / downscale,
+            height as u32 / downscale,
+            yuv_to_rgba(readable.as_slice(), width as _, height as _, downscale),
+        );
+
+        // the receiver is gone, nobody is waiting for more thumbnails
+        if sender.unbounded_send(Ok(thumbnail)).is_err() {
+            break;
+        }
+    }
+
+    Ok(())
+}
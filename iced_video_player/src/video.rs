@@ -1,10 +1,14 @@
+use crate::thumbnails::Thumbnails;
 use crate::Error;
 use gstreamer as gst;
 use gstreamer_app as gst_app;
 use gstreamer_app::prelude::*;
+use iced::futures::channel::oneshot;
 use iced::widget::image as img;
+use std::collections::VecDeque;
 use std::num::NonZeroU8;
 use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
@@ -20,6 +24,18 @@ pub enum Position {
     Frame(u64),
 }
 
+impl Position {
+    /// The time of the position in media playing at `framerate` frames per second.
+    ///
+    /// Many demuxers don't support seeking in frames (`gst::format::Default`), so frames are converted to time.
+    pub(crate) fn to_time(self, framerate: f64) -> Duration {
+        match self {
+            Position::Time(time) => time,
+            Position::Frame(frame) => Duration::from_secs_f64(frame as f64 / framerate),
+        }
+    }
+}
+
 impl From<Position> for gst::GenericFormattedValue {
     fn from(pos: Position) -> Self {
         match pos {
@@ -41,8 +57,112 @@ impl From<u64> for Position {
     }
 }
 
+/// Playback diagnostics for a [`Video`], as returned by [`Video::stats`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stats {
+    /// Number of frames pulled from the decoder while playing.
+    pub frames_decoded: u64,
+    /// Number of decoded frames that were replaced before they could be presented.
+    pub frames_dropped: u64,
+    /// Number of frames uploaded to the GPU by a [`VideoPlayer`](crate::VideoPlayer).
+    pub frames_presented: u64,
+    /// Current averaged offset applied to keep audio in sync with presented frames.
+    pub av_offset: Duration,
+    /// Buffer fill level as a percentage, if the pipeline answers buffering queries.
+    pub buffer_fill: Option<u8>,
+    /// Caps negotiated on the video sink, if any.
+    pub caps: Option<String>,
+    /// Names of the decoder elements currently in the pipeline.
+    pub decoders: Vec<String>,
+}
+
+/// Kind of elementary stream exposed by a `playbin` pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrackKind {
+    Video,
+    Audio,
+    Subtitle,
+}
+
+impl TrackKind {
+    fn playbin_name(self) -> &'static str {
+        match self {
+            TrackKind::Video => "video",
+            TrackKind::Audio => "audio",
+            TrackKind::Subtitle => "text",
+        }
+    }
+}
+
+/// A video, audio or subtitle stream in the media, as listed by [`Video::tracks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Track {
+    /// Index of the stream among the streams of the same kind.
+    pub index: usize,
+    pub kind: TrackKind,
+    /// Language code tag (e.g., `en` or `jpn`), if the container provides one.
+    pub language: Option<String>,
+    /// Codec description tag, if the demuxer provides one.
+    pub codec: Option<String>,
+    /// Human readable stream title, if the container provides one.
+    pub title: Option<String>,
+}
+
+/// Options for loading a [`Video`] from a URI, see [`Video::with_options`] and [`Video::load`].
+#[derive(Debug, Clone)]
+pub struct VideoOptions {
+    /// How long to wait for the media to preroll (i.e., for its resolution to be known) before giving up.
+    pub preroll_timeout: Duration,
+    /// Download progressive network media to disk instead of only buffering it in memory,
+    /// so that the downloaded range can be seeked without fetching it again.
+    /// Adaptive streams (e.g., HLS) are always buffered in memory.
+    pub download: bool,
+    /// Directory for downloaded media, defaults to the system temporary directory.
+    pub cache_dir: Option<PathBuf>,
+}
+
+impl Default for VideoOptions {
+    fn default() -> Self {
+        VideoOptions {
+            preroll_timeout: Duration::from_secs(5),
+            download: false,
+            cache_dir: None,
+        }
+    }
+}
+
+/// Something that happened during playback, reported through [`VideoPlayer::on_event`](crate::VideoPlayer::on_event).
+#[derive(Debug, Clone, PartialEq)]
+pub enum VideoEvent {
+    /// The pipeline reported an error. Check [`Video::failed`] to know whether it is recovering from it.
+    Error(glib::Error),
+    /// The pipeline reported a problem that doesn't stop playback.
+    Warning(glib::Error),
+    /// Buffering progress of a network stream, in percent.
+    Buffering(i32),
+    /// The pipeline changed state (e.g., from paused to playing).
+    StateChanged { old: gst::State, current: gst::State },
+    /// The duration of the media became known or changed.
+    DurationChanged(Duration),
+    /// The end of the media was reached.
+    Eos,
+    /// A new media started playing (e.g., the next item of a playlist).
+    StreamStart,
+}
+
+/// What a [`Video`] does when its decoder fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RecoveryPolicy {
+    /// Stop at the error, [`Video::failed`] returns `true`.
+    #[default]
+    None,
+    /// Tear the pipeline down and resume from the last position, up to `max_attempts` times
+    /// before giving up. The count resets each time the media plays through to its end.
+    Rebuild { max_attempts: u32 },
+}
+
 #[derive(Debug)]
-pub(crate) struct Frame(gst::Sample);
+pub(crate) struct Frame(pub(crate) gst::Sample);
 
 impl Frame {
     pub fn empty() -> Self {
@@ -52,6 +172,15 @@ impl Frame {
     pub fn readable(&self) -> Option<gst::BufferMap<gst::buffer::Readable>> {
         self.0.buffer().and_then(|x| x.map_readable().ok())
     }
+
+    /// Resolution of the frame as negotiated in its caps, with the width padded to mod4.
+    pub fn size(&self) -> Option<(i32, i32)> {
+        let caps = self.0.caps()?;
+        let s = caps.structure(0)?;
+        let width = s.get::<i32>("width").ok()?;
+        let height = s.get::<i32>("height").ok()?;
+        Some((((width + 4 - 1) / 4) * 4, height))
+    }
 }
 
 #[derive(Debug)]
@@ -61,6 +190,7 @@ pub(crate) struct Internal {
 
     pub(crate) bus: gst::Bus,
     pub(crate) source: gst::Pipeline,
+    pub(crate) video_pad: gst::Pad,
     pub(crate) alive: Arc<AtomicBool>,
     pub(crate) worker: Option<std::thread::JoinHandle<()>>,
 
@@ -73,50 +203,64 @@ pub(crate) struct Internal {
 
     pub(crate) frame: Arc<Mutex<Frame>>,
     pub(crate) upload_frame: Arc<AtomicBool>,
+    pub(crate) cpu_frame: Option<img::Handle>,
     pub(crate) last_frame_time: Arc<Mutex<Instant>>,
-    pub(crate) looping: bool,
+    pub(crate) looping: Arc<AtomicBool>,
+    pub(crate) playlist: Arc<Mutex<VecDeque<url::Url>>>,
     pub(crate) is_eos: bool,
     pub(crate) restart_stream: bool,
     pub(crate) sync_av_avg: u64,
     pub(crate) sync_av_counter: u64,
 
+    pub(crate) frames_decoded: Arc<AtomicU64>,
+    pub(crate) frames_dropped: Arc<AtomicU64>,
+    pub(crate) frames_presented: u64,
+
     pub(crate) subtitle_text: Arc<Mutex<Option<String>>>,
     pub(crate) upload_text: Arc<AtomicBool>,
+
+    pub(crate) streams_changed: Arc<AtomicBool>,
+
+    pub(crate) recovery_policy: RecoveryPolicy,
+    pub(crate) recovery_attempts: u32,
+    pub(crate) recovery_position: Option<Duration>,
+    pub(crate) failed: bool,
+
+    pub(crate) live: bool,
+    pub(crate) buffering: Option<i32>,
+    pub(crate) resume_after_buffering: bool,
+
+    pub(crate) scrub_position: Option<Duration>,
+    pub(crate) resume_after_scrub: bool,
 }
 
 impl Internal {
     pub(crate) fn seek(&self, position: impl Into<Position>, accurate: bool) -> Result<(), Error> {
-        let position = position.into();
-
-        // gstreamer complains if the start & end value types aren't the same
-        match &position {
-            Position::Time(_) => self.source.seek(
-                self.speed,
-                gst::SeekFlags::FLUSH
-                    | if accurate {
-                        gst::SeekFlags::ACCURATE
-                    } else {
-                        gst::SeekFlags::empty()
-                    },
-                gst::SeekType::Set,
-                gst::GenericFormattedValue::from(position),
-                gst::SeekType::Set,
-                gst::ClockTime::NONE,
-            )?,
-            Position::Frame(_) => self.source.seek(
-                self.speed,
-                gst::SeekFlags::FLUSH
-                    | if accurate {
-                        gst::SeekFlags::ACCURATE
-                    } else {
-                        gst::SeekFlags::empty()
-                    },
-                gst::SeekType::Set,
-                gst::GenericFormattedValue::from(position),
-                gst::SeekType::Set,
-                gst::format::Default::NONE,
-            )?,
-        };
+        self.seek_with_flags(
+            position,
+            if accurate {
+                gst::SeekFlags::ACCURATE
+            } else {
+                gst::SeekFlags::empty()
+            },
+        )
+    }
+
+    pub(crate) fn seek_with_flags(
+        &self,
+        position: impl Into<Position>,
+        flags: gst::SeekFlags,
+    ) -> Result<(), Error> {
+        let position = self.position_time(position.into());
+
+        self.source.seek(
+            self.speed,
+            gst::SeekFlags::FLUSH | flags,
+            gst::SeekType::Set,
+            gst::ClockTime::from_nseconds(position.as_nanos() as _),
+            gst::SeekType::Set,
+            gst::ClockTime::NONE,
+        )?;
 
         *self.subtitle_text.lock().expect("lock subtitle_text") = None;
         self.upload_text.store(true, Ordering::SeqCst);
@@ -124,6 +268,22 @@ impl Internal {
         Ok(())
     }
 
+    pub(crate) fn position_time(&self, position: Position) -> Duration {
+        position.to_time(self.framerate)
+    }
+
+    /// The appsink the video frames arrive at.
+    pub(crate) fn video_sink(&self) -> Result<gst::Element, Error> {
+        self.video_pad
+            .parent_element()
+            .ok_or_else(|| Error::AppSink("iced_video".to_string()))
+    }
+
+    /// Index of the frame shown at `time`.
+    pub(crate) fn frame_at(&self, time: Duration) -> u64 {
+        (time.as_secs_f64() * self.framerate).round() as u64
+    }
+
     pub(crate) fn set_speed(&mut self, speed: f64) -> Result<(), Error> {
         let Some(position) = self.source.query_position::<gst::ClockTime>() else {
             return Err(Error::Caps);
@@ -158,7 +318,74 @@ impl Internal {
         Ok(())
     }
 
+    /// Rebuilds the pipeline after a decoder error according to the recovery policy.
+    /// Returns `false` if it gave up, the seek back happens once the pipeline prerolled again.
+    pub(crate) fn recover(&mut self) -> bool {
+        let RecoveryPolicy::Rebuild { max_attempts } = self.recovery_policy else {
+            return false;
+        };
+        if self.recovery_attempts >= max_attempts {
+            return false;
+        }
+        self.recovery_attempts += 1;
+
+        let position = self
+            .source
+            .query_position::<gst::ClockTime>()
+            .map(|position| Duration::from_nanos(position.nseconds()));
+        log::warn!(
+            "rebuilding pipeline after error (attempt {}/{max_attempts})",
+            self.recovery_attempts
+        );
+
+        let paused = self.paused();
+        if self.source.set_state(gst::State::Null).is_err()
+            || self
+                .source
+                .set_state(if paused {
+                    gst::State::Paused
+                } else {
+                    gst::State::Playing
+                })
+                .is_err()
+        {
+            return false;
+        }
+        self.recovery_position = position;
+        self.is_eos = false;
+        self.failed = false;
+        true
+    }
+
+    /// Pauses a network stream while its buffer refills and resumes it once full, unless the user paused it meanwhile.
+    pub(crate) fn handle_buffering(&mut self, percent: i32) {
+        // live sources can't be paused to wait for data, they would just fall behind
+        if self.live {
+            return;
+        }
+
+        if percent < 100 {
+            if self.buffering.is_none() {
+                self.resume_after_buffering = !self.paused();
+                if self.resume_after_buffering {
+                    let _ = self.source.set_state(gst::State::Paused);
+                }
+            }
+            self.buffering = Some(percent);
+        } else if self.buffering.take().is_some() && self.resume_after_buffering {
+            let _ = self.source.set_state(gst::State::Playing);
+        }
+    }
+
     pub(crate) fn set_paused(&mut self, paused: bool) {
+        // the pipeline stays paused until the buffer is full, just remember what to do afterwards
+        if self.buffering.is_some() {
+            self.resume_after_buffering = !paused;
+            if !paused {
+                return;
+            }
+        }
+
         self.source
             .set_state(if paused {
                 gst::State::Paused
@@ -174,6 +401,10 @@ impl Internal {
     }
 
     pub(crate) fn paused(&self) -> bool {
+        // a stream paused to refill its buffer still counts as playing
+        if self.buffering.is_some() {
+            return !self.resume_after_buffering;
+        }
         self.source.state(gst::ClockTime::ZERO).1 == gst::State::Paused
     }
 
@@ -225,14 +456,49 @@ impl Drop for Video {
 impl Video {
     /// Create a new video player from a given video which loads from `uri`.
     /// Note that live sources will report the duration to be zero.
+    ///
+    /// This blocks until the media prerolled, use [`Video::load`] for network media.
     pub fn new(uri: url::Url) -> Result<Self, Error> {
+        Self::with_options(uri, VideoOptions::default())
+    }
+
+    /// Loads the video on a separate thread so that slow network sources don't block the caller
+    /// (e.g., from a `Task::perform` in the application's `update`).
+    pub async fn load(uri: url::Url, options: VideoOptions) -> Result<Self, Error> {
+        let (sender, receiver) = oneshot::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(Self::with_options(uri, options));
+        });
+        receiver.await.map_err(|_| Error::Sync)?
+    }
+
+    /// Create a new video player from `uri` with the given options.
+    pub fn with_options(uri: url::Url, options: VideoOptions) -> Result<Self, Error> {
         gst::init()?;
 
-        let pipeline = format!("playbin uri=\"{}\" text-sink=\"appsink name=iced_text sync=true drop=true\" video-sink=\"videoscale ! videoconvert ! appsink name=iced_video drop=true caps=video/x-raw,format=NV12,pixel-aspect-ratio=1/1\"", &uri.as_str());
+        // the default playbin flags, plus on-disk buffering if requested
+        let flags = if options.download {
+            "video+audio+text+soft-volume+deinterlace+soft-colorbalance+download"
+        } else {
+            "video+audio+text+soft-volume+deinterlace+soft-colorbalance"
+        };
+        let pipeline = format!("playbin uri=\"{}\" flags={flags} text-sink=\"appsink name=iced_text sync=true drop=true\" video-sink=\"videoscale ! videoconvert ! appsink name=iced_video drop=true caps=video/x-raw,format=NV12,pixel-aspect-ratio=1/1\"", &uri.as_str());
         let pipeline = gst::parse::launch(pipeline.as_ref())?
             .downcast::<gst::Pipeline>()
             .map_err(|_| Error::Cast)?;
 
+        if let Some(cache_dir) = options.cache_dir.filter(|_| options.download) {
+            let template = cache_dir.join("iced-video-XXXXXX");
+            let template = template.to_string_lossy().into_owned();
+            pipeline.connect("deep-element-added", false, move |args| {
+                let element = args[2].get::<gst::Element>().ok()?;
+                if element.has_property("temp-template", None) {
+                    element.set_property("temp-template", &template);
+                }
+                None
+            });
+        }
+
         let video_sink: gst::Element = pipeline.property("video-sink");
         let pad = video_sink.pads().first().cloned().unwrap();
         let pad = pad.dynamic_cast::<gst::GhostPad>().unwrap();
@@ -247,11 +513,12 @@ impl Video {
         let text_sink: gst::Element = pipeline.property("text-sink");
         let text_sink = text_sink.downcast::<gst_app::AppSink>().unwrap();
 
-        Self::from_gst_pipeline(
+        Self::from_pipeline(
             // uri, 
             pipeline, 
             video_sink, 
-            Some(text_sink)
+            Some(text_sink),
+            options.preroll_timeout,
         )
     }
 
@@ -268,6 +535,15 @@ impl Video {
         pipeline: gst::Pipeline,
         video_sink: gst_app::AppSink,
         text_sink: Option<gst_app::AppSink>,
+    ) -> Result<Self, Error> {
+        Self::from_pipeline(pipeline, video_sink, text_sink, VideoOptions::default().preroll_timeout)
+    }
+
+    fn from_pipeline(
+        pipeline: gst::Pipeline,
+        video_sink: gst_app::AppSink,
+        text_sink: Option<gst_app::AppSink>,
+        preroll_timeout: Duration,
     ) -> Result<Self, Error> {
         gst::init()?;
         static NEXT_ID: AtomicU64 = AtomicU64::new(0);
@@ -286,10 +562,20 @@ impl Video {
 
         let pad = video_sink.pads().first().cloned().unwrap();
 
-        cleanup!(pipeline.set_state(gst::State::Playing))?;
+        // live sources don't preroll, their caps show up once data flows
+        let live = cleanup!(pipeline.set_state(gst::State::Playing))?
+            == gst::StateChangeSuccess::NoPreroll;
 
-        // wait for up to 5 seconds until the decoder gets the source capabilities
-        cleanup!(pipeline.state(gst::ClockTime::from_seconds(5)).0)?;
+        // wait until the decoder gets the source capabilities
+        let deadline = Instant::now() + preroll_timeout;
+        let timeout = gst::ClockTime::from_nseconds(preroll_timeout.as_nanos() as u64);
+        if cleanup!(pipeline.state(timeout).0)? == gst::StateChangeSuccess::Async {
+            let _ = pipeline.set_state(gst::State::Null);
+            return Err(Error::Timeout);
+        }
+        while live && pad.current_caps().is_none() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
 
         // extract resolution and framerate
         // TODO(jazzfool): maybe we want to extract some other information too?
@@ -336,23 +622,70 @@ impl Video {
         let subtitle_text_ref = Arc::clone(&subtitle_text);
         let upload_text_ref = Arc::clone(&upload_text);
 
+        let frames_decoded = Arc::new(AtomicU64::new(0));
+        let frames_dropped = Arc::new(AtomicU64::new(0));
+        let frames_decoded_ref = Arc::clone(&frames_decoded);
+        let frames_dropped_ref = Arc::clone(&frames_dropped);
+
+        // playbin notifies stream changes from its streaming threads, so just flag them for the widget
+        let streams_changed = Arc::new(AtomicBool::new(false));
+        if pipeline.has_property("n-audio", None) {
+            for signal in ["video-changed", "audio-changed", "text-changed"] {
+                let streams_changed_ref = Arc::clone(&streams_changed);
+                pipeline.connect(signal, false, move |_| {
+                    streams_changed_ref.store(true, Ordering::SeqCst);
+                    None
+                });
+            }
+        }
+
+        // playbin asks for the next uri shortly before the current one ends;
+        // switching there instead of seeking on EOS keeps loops and sequences gapless
+        let looping = Arc::new(AtomicBool::new(false));
+        let playlist = Arc::new(Mutex::new(VecDeque::new()));
+        if pipeline.has_property("uri", None) {
+            let looping_ref = Arc::clone(&looping);
+            let playlist_ref = Arc::clone(&playlist);
+            pipeline.connect("about-to-finish", false, move |args| {
+                let playbin = args[0].get::<gst::Element>().ok()?;
+                let next = playlist_ref
+                    .lock()
+                    .ok()?
+                    .pop_front()
+                    .map(|uri: url::Url| uri.to_string());
+                let next = next.or_else(|| {
+                    looping_ref
+                        .load(Ordering::SeqCst)
+                        .then(|| playbin.property::<Option<String>>("current-uri"))
+                        .flatten()
+                });
+                if let Some(uri) = next {
+                    playbin.set_property("uri", uri);
+                }
+                None
+            });
+        }
+
         let pipeline_ref = pipeline.clone();
 
         let worker = std::thread::spawn(move || {
             let mut clear_subtitles_at = None;
+            // only the first of a run of failed pulls is posted, the worker retries every 16ms
+            let mut reported = false;
 
             while alive_ref.load(Ordering::Acquire) {
-                if let Err(gst::FlowError::Error) = (|| -> Result<(), gst::FlowError> {
-                    let sample =
-                        if pipeline_ref.state(gst::ClockTime::ZERO).1 != gst::State::Playing {
-                            video_sink
-                                .try_pull_preroll(gst::ClockTime::from_mseconds(16))
-                                .ok_or(gst::FlowError::Eos)?
-                        } else {
-                            video_sink
-                                .try_pull_sample(gst::ClockTime::from_mseconds(16))
-                                .ok_or(gst::FlowError::Eos)?
-                        };
+                match (|| -> Result<(), gst::FlowError> {
+                    let playing =
+                        pipeline_ref.state(gst::ClockTime::ZERO).1 == gst::State::Playing;
+                    let sample = if !playing {
+                        video_sink
+                            .try_pull_preroll(gst::ClockTime::from_mseconds(16))
+                            .ok_or(gst::FlowError::Eos)?
+                    } else {
+                        video_sink
+                            .try_pull_sample(gst::ClockTime::from_mseconds(16))
+                            .ok_or(gst::FlowError::Eos)?
+                    };
 
                     *last_frame_time_ref
                         .lock()
@@ -368,7 +701,14 @@ impl Video {
                         *frame_guard = Frame(sample);
                     }
 
-                    upload_frame_ref.swap(true, Ordering::SeqCst);
+                    // the preroll sample is pulled repeatedly while paused, so only count real playback
+                    let replaced = upload_frame_ref.swap(true, Ordering::SeqCst);
+                    if playing {
+                        frames_decoded_ref.fetch_add(1, Ordering::Relaxed);
+                        if replaced {
+                            frames_dropped_ref.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
 
                     if let Some(at) = clear_subtitles_at {
                         if frame_pts >= at {
@@ -421,7 +761,21 @@ impl Video {
 
                     Ok(())
                 })() {
-                    log::error!("error pulling frame");
+                    Ok(()) => reported = false,
+                    Err(gst::FlowError::Error) => {
+                        log::error!("error pulling frame");
+                        if !reported {
+                            reported = true;
+                            let warning = gst::message::Warning::builder(
+                                gst::CoreError::Failed,
+                                "failed to pull a frame from the video sink",
+                            )
+                            .src(&video_sink)
+                            .build();
+                            let _ = pipeline_ref.post_message(warning);
+                        }
+                    }
+                    Err(_) => {}
                 }
             }
         });
@@ -432,6 +786,7 @@ impl Video {
 
             bus: pipeline.bus().unwrap(),
             source: pipeline,
+            video_pad: pad,
             alive,
             worker: Some(worker),
 
@@ -444,15 +799,35 @@ impl Video {
 
             frame,
             upload_frame,
+            cpu_frame: None,
             last_frame_time,
-            looping: false,
+            looping,
+            playlist,
             is_eos: false,
             restart_stream: false,
             sync_av_avg: 0,
             sync_av_counter: 0,
 
+            frames_decoded,
+            frames_dropped,
+            frames_presented: 0,
+
             subtitle_text,
             upload_text,
+
+            streams_changed,
+
+            recovery_policy: RecoveryPolicy::default(),
+            recovery_attempts: 0,
+            recovery_position: None,
+            failed: false,
+
+            live,
+            buffering: None,
+            resume_after_buffering: false,
+
+            scrub_position: None,
+            resume_after_scrub: false,
         })))
     }
 
@@ -507,14 +882,96 @@ impl Video {
         self.read().is_eos
     }
 
+    /// Get if the media is a live source (e.g., a live stream), which has no duration and can't be seeked.
+    pub fn is_live(&self) -> bool {
+        self.read().live
+    }
+
+    /// Get the buffering progress in percent while a network stream refills its buffer, `None` otherwise.
+    pub fn buffering(&self) -> Option<i32> {
+        self.read().buffering
+    }
+
+    /// Get if the pipeline stopped on an error it did not (or could not) recover from.
+    pub fn failed(&self) -> bool {
+        self.read().failed
+    }
+
+    /// Get what happens when the decoder fails.
+    pub fn recovery_policy(&self) -> RecoveryPolicy {
+        self.read().recovery_policy
+    }
+
+    /// Set what happens when the decoder fails.
+    pub fn set_recovery_policy(&mut self, policy: RecoveryPolicy) {
+        let inner = self.get_mut();
+        inner.recovery_policy = policy;
+        inner.recovery_attempts = 0;
+    }
+
+    /// Get the last decoded frame as an RGBA image, e.g. to keep showing a still after [`Video::failed`].
+    pub fn current_frame(&self) -> Option<img::Handle> {
+        let inner = self.read();
+        let frame = inner.frame.lock().ok()?;
+        let (width, height) = frame.size()?;
+        let buffer = frame.0.buffer()?.map_readable().ok()?;
+        let rgba = nv12_to_rgba(buffer.as_slice(), width as _, height as _)?;
+        Some(img::Handle::from_rgba(width as _, height as _, rgba))
+    }
+
     /// Get if the media will loop or not.
     pub fn looping(&self) -> bool {
-        self.read().looping
+        self.read().looping.load(Ordering::SeqCst)
     }
 
     /// Set if the media will loop or not.
+    ///
+    /// With a `playbin` pipeline, the last media of the playlist is looped gaplessly.
     pub fn set_looping(&mut self, looping: bool) {
-        self.get_mut().looping = looping;
+        self.get_mut().looping.store(looping, Ordering::SeqCst);
+    }
+
+    /// Queue media to play once the current one finishes, without a gap in between.
+    ///
+    /// For example, queueing an idle clip after an intro clip and enabling [`Video::set_looping`]
+    /// plays the intro once, then loops the idle clip. Requires a `playbin` pipeline.
+    pub fn enqueue(&mut self, uri: url::Url) {
+        self.get_mut()
+            .playlist
+            .lock()
+            .expect("lock playlist")
+            .push_back(uri);
+    }
+
+    /// Get the media queued to play after the current one.
+    pub fn queue(&self) -> Vec<url::Url> {
+        self.read()
+            .playlist
+            .lock()
+            .expect("lock playlist")
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Remove all media queued to play after the current one.
+    pub fn clear_queue(&mut self) {
+        self.get_mut().playlist.lock().expect("lock playlist").clear();
+    }
+
+    /// Immediately switch to the next queued media.
+    /// Returns `false` if the queue was empty.
+    pub fn play_next(&mut self) -> Result<bool, Error> {
+        let paused = self.paused();
+        let mut inner = self.get_mut();
+        let Some(uri) = inner.playlist.lock().map_err(|_| Error::Lock)?.pop_front() else {
+            return Ok(false);
+        };
+        inner.source.set_state(gst::State::Ready)?;
+        inner.source.set_property("uri", uri.as_str());
+        inner.is_eos = false;
+        inner.set_paused(paused);
+        Ok(true)
     }
 
     /// Set if the media is paused or not.
@@ -534,6 +991,75 @@ impl Video {
         self.get_mut().seek(position, accurate)
     }
 
+    /// Pauses the media and advances it by `frames` frames.
+    pub fn step_forward(&mut self, frames: u64) -> Result<(), Error> {
+        let mut inner = self.get_mut();
+        inner.set_paused(true);
+        // the step applies to the prerolled frame, so the pause has to settle first
+        inner.source.state(gst::ClockTime::from_mseconds(500)).0?;
+
+        // sent to the video sink alone: the audio sink would count audio buffers rather than frames
+        let step = gst::event::Step::new(gst::format::Buffers::from_u64(frames), 1.0, true, false);
+        if inner.video_sink()?.send_event(step) {
+            Ok(())
+        } else {
+            Err(Error::Step)
+        }
+    }
+
+    /// Pauses the media and moves it back by `frames` frames.
+    ///
+    /// Decoders can't run backwards, so this is an accurate seek which decodes from the previous keyframe.
+    pub fn step_backward(&mut self, frames: u64) -> Result<(), Error> {
+        let mut inner = self.get_mut();
+        inner.set_paused(true);
+        inner.is_eos = false;
+
+        // after a step only the video sink has moved on, the pipeline may still report the audio sink's position
+        let position = inner
+            .video_sink()?
+            .query_position::<gst::ClockTime>()
+            .map_or(Duration::ZERO, |position| Duration::from_nanos(position.nseconds()));
+        let frame = inner.frame_at(position).saturating_sub(frames);
+        inner.seek(Position::Frame(frame), true)
+    }
+
+    /// Moves to `position` while the user drags a seek bar.
+    ///
+    /// Playback is paused and only keyframes are decoded, so intermediate frames show up quickly
+    /// even in long videos. Call [`Video::end_scrub`] once the drag ends.
+    pub fn scrub(&mut self, position: impl Into<Position>) -> Result<(), Error> {
+        let mut inner = self.get_mut();
+        let position = inner.position_time(position.into());
+
+        if inner.scrub_position.is_none() {
+            inner.resume_after_scrub = !inner.paused();
+            inner.set_paused(true);
+        }
+        inner.scrub_position = Some(position);
+        inner.is_eos = false;
+
+        inner.seek_with_flags(position, gst::SeekFlags::KEY_UNIT | gst::SeekFlags::SNAP_NEAREST)
+    }
+
+    /// Ends a scrub: seeks accurately to the last scrubbed position and resumes playback if it was playing.
+    pub fn end_scrub(&mut self) -> Result<(), Error> {
+        let mut inner = self.get_mut();
+        let Some(position) = inner.scrub_position.take() else {
+            return Ok(());
+        };
+
+        inner.seek(position, true)?;
+        let resume = inner.resume_after_scrub;
+        inner.set_paused(!resume);
+        Ok(())
+    }
+
+    /// Get the position the user is scrubbing to, `None` outside of a scrub.
+    pub fn scrub_position(&self) -> Option<Duration> {
+        self.read().scrub_position
+    }
+
     /// Set the playback speed of the media.
     /// The default speed is `1.0`.
     pub fn set_speed(&mut self, speed: f64) -> Result<(), Error> {
@@ -580,65 +1106,205 @@ impl Video {
         url::Url::parse(&self.read().source.property::<String>("suburi")).ok()
     }
 
+    /// Get playback diagnostics: frame counters, A/V offset, buffering and negotiated format.
+    pub fn stats(&self) -> Stats {
+        let inner = self.read();
+
+        let mut buffering = gst::query::Buffering::new(gst::Format::Percent);
+        let buffer_fill = inner
+            .source
+            .query(&mut buffering)
+            .then(|| buffering.percent().1.clamp(0, 100) as u8);
+
+        let decoders = inner
+            .source
+            .iterate_recurse()
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|element| {
+                element
+                    .factory()
+                    .is_some_and(|factory| factory.has_type(gst::ElementFactoryType::DECODER))
+            })
+            .map(|element| element.name().to_string())
+            .collect();
+
+        Stats {
+            frames_decoded: inner.frames_decoded.load(Ordering::Relaxed),
+            frames_dropped: inner.frames_dropped.load(Ordering::Relaxed),
+            frames_presented: inner.frames_presented,
+            av_offset: Duration::from_nanos(inner.sync_av_avg),
+            buffer_fill,
+            caps: inner.video_pad.current_caps().map(|caps| caps.to_string()),
+            decoders,
+        }
+    }
+
+    /// Get the streams of a given kind known to the pipeline, along with their tags.
+    ///
+    /// Requires a `playbin` pipeline; other pipelines report no tracks.
+    pub fn tracks(&self, kind: TrackKind) -> Vec<Track> {
+        let inner = self.read();
+        let name = kind.playbin_name();
+        if !inner.source.has_property(&format!("n-{name}"), None) {
+            return Vec::new();
+        }
+
+        let count = inner.source.property::<i32>(&format!("n-{name}")).max(0) as usize;
+        (0..count)
+            .map(|index| {
+                let tags = inner.source.emit_by_name::<Option<gst::TagList>>(
+                    &format!("get-{name}-tags"),
+                    &[&(index as i32)],
+                );
+                let tags = tags.as_ref();
+
+                let codec = tags.and_then(|tags| match kind {
+                    TrackKind::Video => tags
+                        .get::<gst::tags::VideoCodec>()
+                        .map(|v| v.get().to_string()),
+                    TrackKind::Audio => tags
+                        .get::<gst::tags::AudioCodec>()
+                        .map(|v| v.get().to_string()),
+                    TrackKind::Subtitle => tags
+                        .get::<gst::tags::SubtitleCodec>()
+                        .map(|v| v.get().to_string()),
+                });
+
+                Track {
+                    index,
+                    kind,
+                    language: tags
+                        .and_then(|tags| tags.get::<gst::tags::LanguageCode>())
+                        .map(|v| v.get().to_string()),
+                    codec,
+                    title: tags
+                        .and_then(|tags| tags.get::<gst::tags::Title>())
+                        .map(|v| v.get().to_string()),
+                }
+            })
+            .collect()
+    }
+
+    /// Get the video streams in the media.
+    pub fn video_tracks(&self) -> Vec<Track> {
+        self.tracks(TrackKind::Video)
+    }
+
+    /// Get the audio streams in the media.
+    pub fn audio_tracks(&self) -> Vec<Track> {
+        self.tracks(TrackKind::Audio)
+    }
+
+    /// Get the subtitle streams in the media, including one loaded through [`Video::set_subtitle_url`].
+    pub fn subtitle_tracks(&self) -> Vec<Track> {
+        self.tracks(TrackKind::Subtitle)
+    }
+
+    /// Get the index of the currently selected stream of a given kind.
+    pub fn current_track(&self, kind: TrackKind) -> Option<usize> {
+        let inner = self.read();
+        let property = format!("current-{}", kind.playbin_name());
+        if !inner.source.has_property(&property, None) {
+            return None;
+        }
+        usize::try_from(inner.source.property::<i32>(&property)).ok()
+    }
+
+    /// Switch to the stream of a given kind at `index` (see [`Video::tracks`]).
+    pub fn set_track(&mut self, kind: TrackKind, index: usize) -> Result<(), Error> {
+        let name = kind.playbin_name();
+        let count = self.tracks(kind).len();
+        if index >= count {
+            return Err(Error::Track(index));
+        }
+        self.get_mut()
+            .source
+            .set_property(&format!("current-{name}"), index as i32);
+        Ok(())
+    }
+
+    /// Switch to the video stream at `index`.
+    pub fn set_video_track(&mut self, index: usize) -> Result<(), Error> {
+        self.set_track(TrackKind::Video, index)
+    }
+
+    /// Switch to the audio stream at `index`.
+    pub fn set_audio_track(&mut self, index: usize) -> Result<(), Error> {
+        self.set_track(TrackKind::Audio, index)
+    }
+
+    /// Switch to the subtitle stream at `index`.
+    pub fn set_subtitle_track(&mut self, index: usize) -> Result<(), Error> {
+        self.set_track(TrackKind::Subtitle, index)
+    }
+
     /// Get the underlying GStreamer pipeline.
     pub fn pipeline(&self) -> gst::Pipeline {
         self.read().source.clone()
     }
 
-    /// Generates a list of thumbnails based on a set of positions in the media, downscaled by a given factor.
+    /// Generates thumbnails based on a set of positions in the media, downscaled by a given factor.
     ///
-    /// Slow; only needs to be called once for each instance.
-    /// It's best to call this at the very start of playback, otherwise the position may shift.
-    pub fn thumbnails<I>(
-        &mut self,
-        positions: I,
-        downscale: NonZeroU8,
-    ) -> Result<Vec<img::Handle>, Error>
+    /// The frames are decoded by a separate pipeline on a worker thread, so playback is not disturbed.
+    /// Each step gives up with [`Error::Timeout`] after `timeout`; see [`Thumbnails`] for cancellation.
+    ///
+    /// Requires a `playbin` pipeline, otherwise the stream yields [`Error::Uri`].
+    pub fn thumbnails<I>(&self, positions: I, downscale: NonZeroU8, timeout: Duration) -> Thumbnails
     where
         I: IntoIterator<Item = Position>,
     {
-        let downscale = u8::from(downscale) as u32;
-
-        let paused = self.paused();
-        let muted = self.muted();
-        let pos = self.position();
-
-        self.set_paused(false);
-        self.set_muted(true);
-
-        let out = {
+        let uri = {
             let inner = self.read();
-            let width = inner.width;
-            let height = inner.height;
-            positions
-                .into_iter()
-                .map(|pos| {
-                    inner.seek(pos, true)?;
-                    inner.upload_frame.store(false, Ordering::SeqCst);
-                    while !inner.upload_frame.load(Ordering::SeqCst) {
-                        std::hint::spin_loop();
-                    }
-                    let frame_guard = inner.frame.lock().map_err(|_| Error::Lock)?;
-                    let frame = frame_guard.readable().ok_or(Error::Lock)?;
-
-                    Ok(img::Handle::from_rgba(
-                        inner.width as u32 / downscale,
-                        inner.height as u32 / downscale,
-                        yuv_to_rgba(frame.as_slice(), width as _, height as _, downscale),
-                    ))
-                })
-                .collect()
+            inner
+                .source
+                .has_property("current-uri", None)
+                .then(|| inner.source.property::<Option<String>>("current-uri"))
+                .flatten()
+                .and_then(|uri| url::Url::parse(&uri).ok())
         };
 
-        self.set_paused(paused);
-        self.set_muted(muted);
-        self.seek(pos, true)?;
+        match uri {
+            Some(uri) => Thumbnails::new(&uri, positions, downscale, timeout),
+            None => Thumbnails::failed(Error::Uri),
+        }
+    }
+}
 
-        out
+/// Converts a full NV12 frame to RGBA on the CPU, for renderers without wgpu primitives.
+pub(crate) fn nv12_to_rgba(nv12: &[u8], width: u32, height: u32) -> Option<Vec<u8>> {
+    let y_size = (width * height) as usize;
+    // each interleaved UV pair covers 2x2 pixels, rounded up for odd sizes
+    let uv_stride = width.div_ceil(2) * 2;
+    let uv_size = (uv_stride * height.div_ceil(2)) as usize;
+    if nv12.len() < y_size + uv_size {
+        return None;
     }
+
+    let image = yuvutils_rs::YuvBiPlanarImage {
+        y_plane: &nv12[..y_size],
+        y_stride: width,
+        uv_plane: &nv12[y_size..y_size + uv_size],
+        uv_stride,
+        width,
+        height,
+    };
+
+    let mut rgba = vec![0; y_size * 4];
+    yuvutils_rs::yuv_nv12_to_rgba(
+        &image,
+        &mut rgba,
+        width * 4,
+        yuvutils_rs::YuvRange::Limited,
+        yuvutils_rs::YuvStandardMatrix::Bt601,
+        yuvutils_rs::YuvConversionMode::Balanced,
+    )
+    .ok()?;
+
+    Some(rgba)
 }
 
-fn yuv_to_rgba(yuv: &[u8], width: u32, height: u32, downscale: u32) -> Vec<u8> {
+pub(crate) fn yuv_to_rgba(yuv: &[u8], width: u32, height: u32, downscale: u32) -> Vec<u8> {
     let uv_start = width * height;
     let mut rgba = vec![];
 
@@ -1,18 +1,24 @@
-use crate::{pipeline::VideoPrimitive, video::Video};
+use crate::{
+    pipeline::VideoPrimitive,
+    renderer::VideoRenderer,
+    video::{nv12_to_rgba, Video, VideoEvent},
+};
 use gstreamer as gst;
 use iced::{
-    advanced::{self, layout, widget, Widget},
+    advanced::{self, image, layout, widget, Widget},
     Element,
 };
-use iced_wgpu::primitive::Renderer as PrimitiveRenderer;
-use log::error;
+use log::{error, warn};
 use std::{marker::PhantomData, sync::atomic::Ordering};
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 /// Video player widget which displays the current frame of a [`Video`](crate::Video).
 pub struct VideoPlayer<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer>
 where
-    Renderer: PrimitiveRenderer
+    Renderer: VideoRenderer
 {
     video: &'a Video,
     content_fit: iced::ContentFit,
@@ -20,14 +26,16 @@ where
     height: iced::Length,
     on_end_of_stream: Option<Message>,
     on_new_frame: Option<Message>,
+    on_streams_changed: Option<Message>,
     on_subtitle_text: Option<Box<dyn Fn(Option<String>) -> Message + 'a>>,
     on_error: Option<Box<dyn Fn(&glib::Error) -> Message + 'a>>,
+    on_event: Option<Box<dyn Fn(VideoEvent) -> Message + 'a>>,
     _phantom: PhantomData<(Theme, Renderer)>,
 }
 
 impl<'a, Message, Theme, Renderer> VideoPlayer<'a, Message, Theme, Renderer>
 where
-    Renderer: PrimitiveRenderer
+    Renderer: VideoRenderer
 {
     /// Creates a new video player widget for a given video.
     pub fn new(video: &'a Video) -> Self {
@@ -38,8 +46,10 @@ where
             height: iced::Length::Shrink,
             on_end_of_stream: None,
             on_new_frame: None,
+            on_streams_changed: None,
             on_subtitle_text: None,
             on_error: None,
+            on_event: None,
             _phantom: Default::default(),
         }
     }
@@ -84,10 +94,19 @@ where
         }
     }
 
+    /// Message to send when the set of video, audio or subtitle streams changes
+    /// (e.g., once the media is loaded), see [`Video::tracks`](crate::Video::tracks).
+    pub fn on_streams_changed(self, on_streams_changed: Message) -> Self {
+        VideoPlayer {
+            on_streams_changed: Some(on_streams_changed),
+            ..self
+        }
+    }
+
     /// Message to send when the video receives a new frame.
     pub fn on_subtitle_text<F>(self, on_subtitle_text: F) -> Self
     where
-        F: 'static + Fn(Option<String>) -> Message,
+        F: 'a + Fn(Option<String>) -> Message,
     {
         VideoPlayer {
             on_subtitle_text: Some(Box::new(on_subtitle_text)),
@@ -98,20 +117,65 @@ where
     /// Message to send when the video playback encounters an error.
     pub fn on_error<F>(self, on_error: F) -> Self
     where
-        F: 'static + Fn(&glib::Error) -> Message,
+        F: 'a + Fn(&glib::Error) -> Message,
     {
         VideoPlayer {
             on_error: Some(Box::new(on_error)),
             ..self
         }
     }
+
+    /// Message to send for every playback event (errors, warnings, buffering, state changes, ...).
+    pub fn on_event<F>(self, on_event: F) -> Self
+    where
+        F: 'a + Fn(VideoEvent) -> Message,
+    {
+        VideoPlayer {
+            on_event: Some(Box::new(on_event)),
+            ..self
+        }
+    }
+
+    fn publish_event(&self, shell: &mut advanced::Shell<'_, Message>, event: VideoEvent) {
+        if let Some(ref on_event) = self.on_event {
+            shell.publish(on_event(event));
+        }
+    }
+}
+
+/// Computes where a video of `image_size` is drawn within `bounds` (based on `Image::draw`).
+/// The result may exceed `bounds`, in which case the caller must clip it.
+pub(crate) fn drawing_bounds(
+    content_fit: iced::ContentFit,
+    image_size: iced::Size,
+    bounds: iced::Rectangle,
+) -> iced::Rectangle {
+    let adjusted_fit = content_fit.fit(image_size, bounds.size());
+    let scale = iced::Vector::new(
+        adjusted_fit.width / image_size.width,
+        adjusted_fit.height / image_size.height,
+    );
+    let final_size = image_size * scale;
+
+    let position = match content_fit {
+        iced::ContentFit::None => iced::Point::new(
+            bounds.x + (image_size.width - adjusted_fit.width) / 2.0,
+            bounds.y + (image_size.height - adjusted_fit.height) / 2.0,
+        ),
+        _ => iced::Point::new(
+            bounds.center_x() - final_size.width / 2.0,
+            bounds.center_y() - final_size.height / 2.0,
+        ),
+    };
+
+    iced::Rectangle::new(position, final_size)
 }
 
 impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer>
     for VideoPlayer<'_, Message, Theme, Renderer>
 where
     Message: Clone,
-    Renderer: PrimitiveRenderer,
+    Renderer: VideoRenderer,
 {
     fn size(&self) -> iced::Size<iced::Length> {
         iced::Size {
@@ -158,28 +222,9 @@ where
     ) {
         let mut inner = self.video.write();
 
-        // bounds based on `Image::draw`
         let image_size = iced::Size::new(inner.width as f32, inner.height as f32);
         let bounds = layout.bounds();
-        let adjusted_fit = self.content_fit.fit(image_size, bounds.size());
-        let scale = iced::Vector::new(
-            adjusted_fit.width / image_size.width,
-            adjusted_fit.height / image_size.height,
-        );
-        let final_size = image_size * scale;
-
-        let position = match self.content_fit {
-            iced::ContentFit::None => iced::Point::new(
-                bounds.x + (image_size.width - adjusted_fit.width) / 2.0,
-                bounds.y + (image_size.height - adjusted_fit.height) / 2.0,
-            ),
-            _ => iced::Point::new(
-                bounds.center_x() - final_size.width / 2.0,
-                bounds.center_y() - final_size.height / 2.0,
-            ),
-        };
-
-        let drawing_bounds = iced::Rectangle::new(position, final_size);
+        let drawing_bounds = drawing_bounds(self.content_fit, image_size, bounds);
 
         let upload_frame = inner.upload_frame.swap(false, Ordering::SeqCst);
 
@@ -190,10 +235,11 @@ where
                 .map(|time| *time)
                 .unwrap_or_else(|_| Instant::now());
             inner.set_av_offset(Instant::now() - last_frame_time);
+            inner.frames_presented += 1;
         }
 
-        let render = |renderer: &mut Renderer| {
-            renderer.draw_primitive(
+        let mut render = |renderer: &mut Renderer| {
+            let drawn = renderer.try_draw_primitive(
                 drawing_bounds,
                 VideoPrimitive::new(
                     inner.id,
@@ -203,9 +249,28 @@ where
                     upload_frame,
                 ),
             );
+
+            if !drawn {
+                // no wgpu backend (e.g., tiny-skia), so convert on the CPU whenever the frame changes
+                if upload_frame || inner.cpu_frame.is_none() {
+                    let (width, height) = (inner.width as u32, inner.height as u32);
+                    let rgba = inner.frame.lock().ok().and_then(|frame| {
+                        frame
+                            .readable()
+                            .and_then(|readable| nv12_to_rgba(readable.as_slice(), width, height))
+                    });
+                    if let Some(rgba) = rgba {
+                        inner.cpu_frame = Some(image::Handle::from_rgba(width, height, rgba));
+                    }
+                }
+
+                if let Some(handle) = inner.cpu_frame.clone() {
+                    renderer.draw_image(image::Image::new(handle), drawing_bounds);
+                }
+            }
         };
 
-        if adjusted_fit.width > bounds.width || adjusted_fit.height > bounds.height {
+        if drawing_bounds.width > bounds.width || drawing_bounds.height > bounds.height {
             renderer.with_layer(bounds, render);
         } else {
             render(renderer);
@@ -226,51 +291,123 @@ where
         let mut inner = self.video.write();
 
         if let iced::Event::Window(iced::window::Event::RedrawRequested(_)) = event {
-            if inner.restart_stream || (!inner.is_eos && !inner.paused()) {
-                let mut restart_stream = false;
-                if inner.restart_stream {
-                    restart_stream = true;
-                    // Set flag to false to avoid potentially multiple seeks
-                    inner.restart_stream = false;
+            if inner.streams_changed.swap(false, Ordering::SeqCst) {
+                if let Some(on_streams_changed) = self.on_streams_changed.clone() {
+                    shell.publish(on_streams_changed);
                 }
-                let mut eos_pause = false;
-
-                while let Some(msg) = inner
-                    .bus
-                    .pop_filtered(&[gst::MessageType::Error, gst::MessageType::Eos])
-                {
-                    match msg.view() {
-                        gst::MessageView::Error(err) => {
-                            error!("bus returned an error: {err}");
-                            if let Some(ref on_error) = self.on_error {
-                                shell.publish(on_error(&err.error()))
-                            };
+            }
+
+            let mut restart_stream = false;
+            if inner.restart_stream {
+                restart_stream = true;
+                // Set flag to false to avoid potentially multiple seeks
+                inner.restart_stream = false;
+            }
+            let mut eos_pause = false;
+
+            // drained on every redraw (not only while playing) so state changes and buffering are reported when paused
+            while let Some(msg) = inner.bus.pop_filtered(&[
+                gst::MessageType::Error,
+                gst::MessageType::Warning,
+                gst::MessageType::Eos,
+                gst::MessageType::StreamStart,
+                gst::MessageType::DurationChanged,
+                gst::MessageType::Buffering,
+                gst::MessageType::StateChanged,
+                gst::MessageType::AsyncDone,
+            ]) {
+                match msg.view() {
+                    gst::MessageView::Error(err) => {
+                        error!("bus returned an error: {err}");
+                        if let Some(ref on_error) = self.on_error {
+                            shell.publish(on_error(&err.error()))
+                        };
+
+                        let recovering = err.error().kind::<gst::StreamError>().is_some()
+                            && inner.recover();
+                        if !recovering {
+                            inner.failed = true;
                         }
-                        gst::MessageView::Eos(_eos) => {
-                            if let Some(on_end_of_stream) = self.on_end_of_stream.clone() {
-                                shell.publish(on_end_of_stream);
-                            }
-                            if inner.looping {
-                                restart_stream = true;
-                            } else {
-                                eos_pause = true;
+                        self.publish_event(shell, VideoEvent::Error(err.error()));
+                    }
+                    gst::MessageView::Warning(warning) => {
+                        warn!("bus returned a warning: {warning}");
+                        self.publish_event(shell, VideoEvent::Warning(warning.error()));
+                    }
+                    gst::MessageView::Eos(_eos) => {
+                        if let Some(on_end_of_stream) = self.on_end_of_stream.clone() {
+                            shell.publish(on_end_of_stream);
+                        }
+                        self.publish_event(shell, VideoEvent::Eos);
+                        // a full playthrough means any earlier recovery worked
+                        inner.recovery_attempts = 0;
+                        if inner.looping.load(Ordering::SeqCst) {
+                            restart_stream = true;
+                        } else {
+                            eos_pause = true;
+                        }
+                    }
+                    // a queued media started playing, or the current one learned its length
+                    gst::MessageView::StreamStart(_) | gst::MessageView::DurationChanged(_) => {
+                        if let Some(duration) = inner.source.query_duration::<gst::ClockTime>() {
+                            inner.duration = Duration::from_nanos(duration.nseconds());
+                        }
+                        if let gst::MessageView::StreamStart(_) = msg.view() {
+                            self.publish_event(shell, VideoEvent::StreamStart);
+                        } else {
+                            self.publish_event(shell, VideoEvent::DurationChanged(inner.duration));
+                        }
+                    }
+                    gst::MessageView::Buffering(buffering) => {
+                        inner.handle_buffering(buffering.percent());
+                        self.publish_event(shell, VideoEvent::Buffering(buffering.percent()));
+                    }
+                    gst::MessageView::StateChanged(state_changed) => {
+                        // every element reports its own state changes, only the pipeline's matter
+                        if msg.src() == Some(inner.source.upcast_ref::<gst::Object>()) {
+                            self.publish_event(
+                                shell,
+                                VideoEvent::StateChanged {
+                                    old: state_changed.old(),
+                                    current: state_changed.current(),
+                                },
+                            );
+                        }
+                    }
+                    gst::MessageView::AsyncDone(_) => {
+                        // the pipeline was rebuilt after an error and can be seeked again
+                        if let Some(position) = inner.recovery_position.take() {
+                            if let Err(err) = inner.seek(position, false) {
+                                error!("cannot resume recovered stream: {err:#?}");
                             }
                         }
-                        _ => {}
                     }
+                    _ => {}
                 }
+            }
 
-                // Don't run eos_pause if restart_stream is true; fixes "pausing" after restarting a stream
-                if restart_stream {
-                    if let Err(err) = inner.restart_stream() {
-                        error!("cannot restart stream (can't seek): {err:#?}");
-                    }
-                } else if eos_pause {
-                    inner.is_eos = true;
-                    inner.set_paused(true);
+            // Don't run eos_pause if restart_stream is true; fixes "pausing" after restarting a stream
+            if restart_stream {
+                if let Err(err) = inner.restart_stream() {
+                    error!("cannot restart stream (can't seek): {err:#?}");
                 }
+            } else if eos_pause {
+                inner.is_eos = true;
+                inner.set_paused(true);
+            }
 
+            if !inner.is_eos && !inner.paused() {
                 if inner.upload_frame.load(Ordering::SeqCst) {
+                    // queued media may not share the resolution of the first one
+                    let size = inner.frame.lock().ok().and_then(|frame| frame.size());
+                    if let Some((width, height)) = size {
+                        if (width, height) != (inner.width, inner.height) {
+                            inner.width = width;
+                            inner.height = height;
+                            shell.invalidate_layout();
+                        }
+                    }
+
                     if let Some(on_new_frame) = self.on_new_frame.clone() {
                         shell.publish(on_new_frame);
                     }
@@ -297,7 +434,7 @@ impl<'a, Message, Theme, Renderer> From<VideoPlayer<'a, Message, Theme, Renderer
 where
     Message: 'a + Clone,
     Theme: 'a,
-    Renderer: 'a + PrimitiveRenderer,
+    Renderer: 'a + VideoRenderer,
 {
     fn from(video_player: VideoPlayer<'a, Message, Theme, Renderer>) -> Self {
         Self::new(video_player)
@@ -1,18 +1,22 @@
 // #![windows_subsystem = "windows"]
 mod utils;
 
+#[cfg(test)]
+mod tests;
+
 use file_format::FileFormat;
 use ::image::{DynamicImage, ImageReader};
 use iced::{
-    alignment::Vertical::{Bottom, Top}, border, font, gradient, mouse, wgpu::naga::back, widget::{button, center, column, container, image, mouse_area, row, stack, text, Column, Space}, window::{self, icon, Settings}, Alignment::Center, Color, Element, Font, Length, Point, Renderer, Size, Subscription, Task, Theme
+    alignment::Vertical::{Bottom, Top}, border, font, gradient, keyboard, mouse, time, wgpu::naga::back, widget::{button, center, column, container, image, mouse_area, pick_list, row, stack, text, text_editor, Column, Space}, window::{self, icon, Settings}, Alignment::Center, Color, ContentFit, Element, Font, Length, Point, Renderer, Size, Subscription, Task, Theme
 };
-use iced_video_player::{Video, VideoPlayer};
+use iced_video_player::{Position, Video, VideoEvent, VideoPlayer};
 use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
-use utils::{img_utils::round_image, visual_helper::{get_game_background, get_game_icon, get_game_icon_handle}};
+use tempfile::TempPath;
+use utils::{crash_report::{create_crash_report, get_crash_report_dialog, get_crash_report_dir, CrashInfo, CrashReport, CRASH_OUTPUT_LINES}, debug_overlay::get_debug_overlay, deep_link::{get_deep_link_prompt, parse_deep_link, register_url_scheme, DeepLinkAction, URL_SCHEME}, file_manager::open_in_file_manager, gallery::{cycle_subtitles, get_gallery_items, get_gallery_view, load_gallery_thumbnails, open_gallery_item, Gallery, GalleryViewer}, game_process::poll_running_games, game_registry::get_game_definition, hooks::{get_hook_env, run_hooks, GameHooks, Hook, HookStage}, img_utils::round_image, language::LanguageChoice, logging::{get_diagnostics_view, get_recent_log_lines, init_logging, set_log_filter, Diagnostics, DEFAULT_LOG_FILTER}, paths::get_data_dir, profiles::{export_profile, get_profile_choices, get_profiles_dir, import_profiles, LaunchProfile, ProfileChoice}, runner::{get_prefix_dir, get_runner_view, parse_dll_overrides, parse_env, prepare_prefix, wrap_command, RunnerChoice, RunnerPanel, RunnerSettings}, server_config::{get_server_config_view, ConfigEditor}, server_list::{check_server, get_server_list_view, RemoteServer, ServerChoice, ServerForm, ServerProtocol, ServerStatus, CHECK_INTERVAL}, server_log::{get_log_dir, get_server_log_view, LogLevel, LogViewer}, sessions::{export_sessions, get_sessions_view, load_sessions, record_session, ExportFormat, Session}, settings::{get_settings_view, parse_install_dir, SettingsPanel}, single_instance::{claim_single_instance, get_forwarded_commands, parse_launcher_command, LauncherCommand}, supervisor::{get_client_command, get_server_command, ProcessKind, Supervisor, SupervisorError}, title_bar::{get_resize_handles, get_title_bar}, tray::{get_tray_actions, spawn_tray, Tray, TrayAction, TrayMenuState}, visual_helper::{get_game_background, get_game_icon, get_game_icon_handle, get_placeholder_background, select_preferred_tracks}};
 use std::{
-    collections::HashMap, env, fs::{self, create_dir_all, read_to_string}, io::{Cursor, Read, Write}, path::PathBuf, sync::Arc
+    collections::{HashMap, HashSet}, env, fs::{self, create_dir_all, read_to_string}, io::{Cursor, Read, Write}, num::NonZeroU8, path::PathBuf, sync::Arc, time::Duration
 };
 
 #[derive(rust_embed::Embed)]
@@ -20,6 +24,22 @@ use std::{
 struct Assets;
 
 pub fn main() -> iced::Result {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !claim_single_instance(&args) {
+        // the running launcher focuses itself and handles the arguments
+        return Ok(());
+    }
+    let saved_state = SavedState::load().unwrap_or_default();
+    // flushes the log file when main returns
+    let _log_guard = init_logging(saved_state.log_filter.as_deref().unwrap_or(DEFAULT_LOG_FILTER));
+
+    // keep the link handler pointing at this executable, it may have moved since the last run
+    std::thread::spawn(|| {
+        if let Err(err) = register_url_scheme() {
+            tracing::warn!("Failed to register the {URL_SCHEME}:// link handler: {err}");
+        }
+    });
+
     let icon_file = Assets::get("icon.png").unwrap();
     let icon_image = ImageReader::new(Cursor::new(icon_file.data))
         .with_guessed_format()
@@ -28,30 +48,37 @@ pub fn main() -> iced::Result {
         .unwrap();
     let rgba_vec = icon_image.as_rgba8().unwrap().to_vec();
 
+    let geometry = saved_state.window.unwrap_or_default();
+
     let settings = Settings {
         decorations: false,
         icon: Some(icon::from_rgba(rgba_vec, icon_image.width(), icon_image.height()).unwrap()),
-        size: Size::new(2000.0, 1000.0),
-        maximized: false,
+        size: Size::new(geometry.width, geometry.height),
+        maximized: geometry.maximized,
         fullscreen: false,
-        position: window::Position::Centered,
+        position: geometry.position(),
         max_size: None,
-        min_size: None,
+        min_size: Some(MIN_WINDOW_SIZE),
         visible: true,
-        resizable: false,
+        resizable: true,
         transparent: false,
         level: window::Level::Normal,
-        exit_on_close_request: true,
+        // closing saves the window geometry first
+        exit_on_close_request: false,
         ..Settings::default()
     };
 
-    iced::application(Launcher::boot, Launcher::update, Launcher::view)
+    iced::application(move || Launcher::boot(saved_state.clone(), args.clone()), Launcher::update, Launcher::view)
         .title(Launcher::title)
+        .subscription(Launcher::subscription)
         .window(settings)
-        .window_size((1280.0, 760.0))
         .run()
 }
 
+const MIN_WINDOW_SIZE: Size = Size::new(960.0, 540.0);
+// below this width the bars switch to smaller text and icons
+const COMPACT_WIDTH: f32 = 1100.0;
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, EnumIter, Default, Serialize, Deserialize)]
 enum PossibleGames {
     #[default]
@@ -67,9 +94,55 @@ enum Launcher {
     Loaded(Box<State>),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct WindowGeometry {
+    // `None` centers the window
+    position: Option<(f32, f32)>,
+    width: f32,
+    height: f32,
+    maximized: bool,
+    // the monitor the window was last on, to notice when it comes back on another one
+    monitor: Option<MonitorInfo>,
+}
+
+/// What iced tells about a monitor, there is no name or position to identify it by.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct MonitorInfo {
+    width: f32,
+    height: f32,
+    scale_factor: f32,
+}
+
+impl Default for WindowGeometry {
+    fn default() -> Self {
+        WindowGeometry { position: None, width: 1280.0, height: 760.0, maximized: false, monitor: None }
+    }
+}
+
+impl WindowGeometry {
+    fn position(&self) -> window::Position {
+        match self.position {
+            Some((x, y)) => window::Position::Specific(Point::new(x, y)),
+            None => window::Position::Centered,
+        }
+    }
+}
+
+/// The monitor the window is on, `None` when it is on none of them, e.g. restored onto one that was unplugged.
+fn get_monitor_info() -> Task<Option<MonitorInfo>> {
+    window::get_latest().and_then(|id| {
+        window::monitor_size(id).then(move |size| {
+            window::scale_factor(id).map(move |scale_factor| {
+                size.map(|size| MonitorInfo { width: size.width, height: size.height, scale_factor })
+            })
+        })
+    })
+}
+
 #[derive(Debug)]
 enum LauncherBackground {
-    Video(Video),
+    // the temp file backs the video's uri and is reopened on every gapless loop
+    Video(Video, TempPath),
     Image(image::Handle),
 }
 
@@ -77,12 +150,37 @@ enum LauncherBackground {
 impl LauncherBackground {
     fn inner(&self) -> Element<Message> {
         match self {
-            LauncherBackground::Video(video) => VideoPlayer::new(video).into(),
-            LauncherBackground::Image(handle) => image(handle).into(),
+            LauncherBackground::Video(video, _) => VideoPlayer::new(video)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .content_fit(ContentFit::Cover)
+                .into(),
+            LauncherBackground::Image(handle) => image(handle)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .content_fit(ContentFit::Cover)
+                .into(),
         }
     }
 }
 
+/// Everything needed to start a client, worked out before the pre-launch hooks run.
+#[derive(Debug, Clone)]
+struct LaunchPlan {
+    game: PossibleGames,
+    profile: Option<LaunchProfile>,
+    install_dir: PathBuf,
+    server: Option<(String, u16)>,
+    // the selected server is the local one, started after the pre-launch hooks succeed
+    local_server: bool,
+}
+
+impl LaunchPlan {
+    fn get_hook_env(&self) -> Vec<(String, String)> {
+        get_hook_env(&self.game, &self.install_dir, self.profile.as_ref(), self.server.as_ref())
+    }
+}
+
 #[derive(Debug, Default)]
 struct State {
     selected_game: PossibleGames,
@@ -90,7 +188,49 @@ struct State {
     installed_game_servers: Vec<PossibleGames>,
     db_software_installed: bool,
     background: Option<LauncherBackground>,
-    icon_images: HashMap<PossibleGames, image::Handle>
+    icon_images: HashMap<PossibleGames, image::Handle>,
+    show_debug_overlay: bool,
+    running_games: HashSet<PossibleGames>,
+    window_hidden: bool,
+    low_power: bool,
+    gallery: Option<Gallery>,
+    window: WindowGeometry,
+    game_installs: HashMap<PossibleGames, PathBuf>,
+    server_installs: HashMap<PossibleGames, PathBuf>,
+    supervisor: Supervisor,
+    tray: Option<Tray>,
+    tray_menu: TrayMenuState,
+    minimize_to_tray_on_launch: bool,
+    minimize_to_tray_on_close: bool,
+    status_message: Option<String>,
+    remote_servers: Vec<RemoteServer>,
+    // a link waiting for the user to confirm it
+    pending_deep_link: Option<DeepLinkAction>,
+    profiles: HashMap<PossibleGames, Vec<LaunchProfile>>,
+    // games without an entry launch with their default setup
+    selected_profiles: HashMap<PossibleGames, String>,
+    // games without an entry leave the client's server untouched
+    selected_servers: HashMap<PossibleGames, ServerChoice>,
+    // last check of each remote server by address
+    server_statuses: HashMap<String, ServerStatus>,
+    // the server list is open while its form exists
+    server_list: Option<ServerForm>,
+    config_editor: Option<ConfigEditor>,
+    server_log: Option<LogViewer>,
+    game_hooks: HashMap<PossibleGames, GameHooks>,
+    // clients started by the launcher, for their post-exit hooks
+    active_launches: HashMap<PossibleGames, LaunchPlan>,
+    runner_settings: HashMap<PossibleGames, RunnerSettings>,
+    runner_panel: Option<RunnerPanel>,
+    sessions: Vec<Session>,
+    show_sessions: bool,
+    crash_report: Option<CrashReport>,
+    // `None` uses the default filter
+    log_filter: Option<String>,
+    diagnostics: Option<Diagnostics>,
+    settings_panel: Option<SettingsPanel>,
+    // ISO 639-1 code, `None` follows the system language
+    language: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -98,11 +238,88 @@ struct SavedState {
     installed_games: Vec<PossibleGames>,
     installed_game_servers: Vec<PossibleGames>,
     db_software_installed: bool,
+    #[serde(default)]
+    low_power: bool,
+    #[serde(default)]
+    window: Option<WindowGeometry>,
+    #[serde(default)]
+    game_installs: HashMap<PossibleGames, PathBuf>,
+    #[serde(default)]
+    server_installs: HashMap<PossibleGames, PathBuf>,
+    #[serde(default)]
+    minimize_to_tray_on_launch: bool,
+    #[serde(default)]
+    minimize_to_tray_on_close: bool,
+    #[serde(default)]
+    remote_servers: Vec<RemoteServer>,
+    #[serde(default)]
+    profiles: HashMap<PossibleGames, Vec<LaunchProfile>>,
+    #[serde(default)]
+    selected_profiles: HashMap<PossibleGames, String>,
+    #[serde(default)]
+    selected_servers: HashMap<PossibleGames, ServerChoice>,
+    #[serde(default)]
+    game_hooks: HashMap<PossibleGames, GameHooks>,
+    #[serde(default)]
+    runner_settings: HashMap<PossibleGames, RunnerSettings>,
+    #[serde(default)]
+    log_filter: Option<String>,
+    #[serde(default)]
+    language: Option<String>,
 }
 
 impl From<SavedState> for Box<State> {
     fn from(val: SavedState) -> Self {
-        Box::new(State { installed_games: val.installed_games, installed_game_servers: val.installed_game_servers, db_software_installed: val.db_software_installed, ..State::default() })
+        Box::new(State { installed_games: val.installed_games, installed_game_servers: val.installed_game_servers, db_software_installed: val.db_software_installed, low_power: val.low_power, window: val.window.unwrap_or_default(), game_installs: val.game_installs, server_installs: val.server_installs, minimize_to_tray_on_launch: val.minimize_to_tray_on_launch, minimize_to_tray_on_close: val.minimize_to_tray_on_close, remote_servers: val.remote_servers, profiles: val.profiles, selected_profiles: val.selected_profiles, selected_servers: val.selected_servers, game_hooks: val.game_hooks, runner_settings: val.runner_settings, log_filter: val.log_filter, language: val.language, ..State::default() })
+    }
+}
+
+impl From<&State> for SavedState {
+    fn from(state: &State) -> Self {
+        SavedState {
+            installed_games: state.installed_games.clone(),
+            installed_game_servers: state.installed_game_servers.clone(),
+            db_software_installed: state.db_software_installed,
+            low_power: state.low_power,
+            window: Some(state.window),
+            game_installs: state.game_installs.clone(),
+            server_installs: state.server_installs.clone(),
+            minimize_to_tray_on_launch: state.minimize_to_tray_on_launch,
+            minimize_to_tray_on_close: state.minimize_to_tray_on_close,
+            remote_servers: state.remote_servers.clone(),
+            profiles: state.profiles.clone(),
+            selected_profiles: state.selected_profiles.clone(),
+            selected_servers: state.selected_servers.clone(),
+            game_hooks: state.game_hooks.clone(),
+            runner_settings: state.runner_settings.clone(),
+            log_filter: state.log_filter.clone(),
+            language: state.language.clone(),
+        }
+    }
+}
+
+impl SavedState {
+    fn path() -> Option<PathBuf> {
+        Some(get_data_dir()?.join("launcher-state.json"))
+    }
+
+    fn load() -> Result<SavedState, LoadError> {
+        let path = Self::path().ok_or(LoadError::File)?;
+        let contents = read_to_string(path).map_err(|_| LoadError::File)?;
+
+        serde_json::from_str(&contents).map_err(|_| LoadError::Format)
+    }
+
+    fn save(&self) -> Result<(), SaveError> {
+        let json = serde_json::to_string_pretty(self).map_err(|_| SaveError::Format)?;
+
+        let path = Self::path().ok_or(SaveError::Write)?;
+
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).map_err(|_| SaveError::Write)?;
+        }
+
+        fs::write(path, json.as_bytes()).map_err(|_| SaveError::Write)
     }
 }
 
@@ -124,53 +341,402 @@ enum Message {
     DragStarted,
     GameSelected(PossibleGames),
     Close,
-    Minimize
+    Minimize,
+    ToggleDebugOverlay,
+    RefreshDebugOverlay,
+    VideoStreamsChanged,
+    BackgroundVideoEvent(VideoEvent),
+    WindowEvent(window::Event),
+    PollGameProcesses,
+    GameProcessesPolled(HashSet<PossibleGames>),
+    ToggleLowPower,
+    LowPowerStillReady(Option<image::Handle>),
+    MonitorChecked(Option<MonitorInfo>),
+    MonitorChanged(Option<MonitorInfo>),
+    WindowResized(Size, bool),
+    WindowMoved(Point, bool),
+    ToggleMaximize,
+    ResizeStarted(window::Direction),
+    OpenGallery,
+    GalleryBack,
+    GalleryThumbnailReady(PathBuf, Option<image::Handle>),
+    OpenGalleryItem(usize),
+    GalleryVideoLoaded(PathBuf, Result<Arc<Video>, String>),
+    GalleryTogglePause,
+    GalleryScrub(Duration),
+    GalleryScrubEnded,
+    GalleryVolumeChanged(f64),
+    GalleryCycleSubtitles,
+    GallerySubtitleText(Option<String>),
+    GalleryVideoEnded,
+    LaunchGame(PossibleGames),
+    StartServer(PossibleGames),
+    StopServer(PossibleGames),
+    PollTray,
+    TrayAction(TrayAction),
+    PollInstanceCommands,
+    InstanceCommand(Vec<String>),
+    ConfirmDeepLink,
+    CancelDeepLink,
+    LaunchGameWithProfile(PossibleGames, String),
+    ProfileSelected(ProfileChoice),
+    ExportProfile,
+    ImportProfiles,
+    OpenServerList,
+    CloseServerList,
+    CheckServers,
+    ServerChecked(String, ServerStatus),
+    SelectServer(ServerChoice),
+    RemoveServer(String),
+    ServerFormName(String),
+    ServerFormHost(String),
+    ServerFormPort(String),
+    ServerFormRegion(String),
+    ServerFormToggleProtocol,
+    AddServer,
+    OpenServerConfig(PossibleGames),
+    CloseServerConfig,
+    ConfigFieldChanged(usize, String),
+    ConfigToggleRaw,
+    ConfigRawEdited(text_editor::Action),
+    SaveServerConfig,
+    OpenServerLog(PossibleGames),
+    CloseServerLog,
+    RefreshServerLog,
+    ToggleServerLogFollow,
+    ServerLogFilterChanged(String),
+    ServerLogSearchChanged(String),
+    ServerLogLevelChanged(Option<LogLevel>),
+    OpenLogFolder,
+    PreLaunchHooksFinished(LaunchPlan, Result<(), String>),
+    PostExitHooksFinished(Result<(), String>),
+    OpenRunnerSettings,
+    CloseRunnerSettings,
+    RunnerSelected(RunnerChoice),
+    ToggleDxvk(bool),
+    DllOverridesChanged(String),
+    RunnerEnvChanged(String),
+    PreparePrefix(bool),
+    PrefixPrepared(Result<(), String>),
+    OpenSessions,
+    CloseSessions,
+    ExportSessions(ExportFormat),
+    CrashReportCreated(Result<CrashReport, String>),
+    DismissCrashReport,
+    OpenCrashReportFolder,
+    ToggleDiagnostics,
+    RefreshDiagnostics,
+    LogFilterChanged(String),
+    ApplyLogFilter,
+    OpenSettings,
+    CloseSettings,
+    GameInstallChanged(String),
+    ServerInstallChanged(String),
+    HookCommandChanged(HookStage, String),
+    HookTimeoutChanged(HookStage, String),
+    HookAbortToggled(bool),
+    LanguageSelected(LanguageChoice),
 }
 
 impl State {
-    // fn path() -> PathBuf {
-    //     path.push("launcher-state.json");
+    /// Background playback only runs while the launcher is visible, no game is running and the gallery is closed.
+    fn update_background_playback(&mut self) {
+        let suspended = self.window_hidden || !self.running_games.is_empty() || self.gallery.is_some();
+        if let Some(LauncherBackground::Video(video, _)) = self.background.as_mut() {
+            if video.paused() != suspended {
+                video.set_paused(suspended);
+            }
+        }
+    }
+
+    /// Low power mode swaps the background video for a still of its current frame.
+    fn apply_low_power(&mut self) -> Task<Message> {
+        if self.low_power {
+            if let Some(LauncherBackground::Video(video, _)) = self.background.as_ref() {
+                let position = Position::Time(video.position());
+                let stills = video.thumbnails([position], NonZeroU8::MIN, Duration::from_secs(5));
+                return Task::perform(stills.collect_all(), |stills| {
+                    Message::LowPowerStillReady(stills.ok().and_then(|mut stills| stills.pop()))
+                });
+            }
+        } else if matches!(self.background, Some(LauncherBackground::Image(_))) {
+            self.background = Some(get_game_background(self));
+            self.update_background_playback();
+        }
+        Task::none()
+    }
+
+    fn save(&self) {
+        if let Err(err) = SavedState::from(self).save() {
+            tracing::error!("failed to save launcher state: {err:?}");
+        }
+    }
+
+    /// Saves the hook edited in the settings panel, unless it is invalid.
+    fn save_game_hook(&mut self, stage: HookStage) {
+        let Some(panel) = self.settings_panel.as_mut() else {
+            return;
+        };
+        let parsed = panel.parse_hook(stage);
+        panel.error = parsed.as_ref().err().cloned();
+        let Ok(hook) = parsed else {
+            return;
+        };
+        let hooks = self.game_hooks.entry(panel.game.clone()).or_default();
+        match stage {
+            HookStage::PreLaunch => hooks.pre_launch = hook,
+            HookStage::PostExit => hooks.post_exit = hook,
+        }
+        if *hooks == GameHooks::default() {
+            self.game_hooks.remove(&panel.game);
+        }
+        self.save();
+    }
+
+    fn get_tray_menu_state(&self) -> TrayMenuState {
+        TrayMenuState {
+            games: PossibleGames::iter().map(|game| {
+                let running = self.running_games.contains(&game) || self.supervisor.is_running(&game, ProcessKind::Client);
+                (game, running)
+            }).collect(),
+            servers: PossibleGames::iter()
+                .filter(|game| self.server_installs.contains_key(game))
+                .map(|game| {
+                    let status = self.supervisor.status(&game, ProcessKind::Server);
+                    (game, status)
+                })
+                .collect(),
+            minimize_on_launch: self.minimize_to_tray_on_launch,
+            minimize_on_close: self.minimize_to_tray_on_close,
+        }
+    }
+
+    /// Pushes the supervisor state to the tray menu if it changed since the last push.
+    fn update_tray(&mut self) {
+        let menu = self.get_tray_menu_state();
+        if let Some(tray) = self.tray.as_ref() && menu != self.tray_menu {
+            tray.update(menu.clone());
+            self.tray_menu = menu;
+        }
+    }
+
+    fn hide_to_tray(&mut self) -> Task<Message> {
+        self.window_hidden = true;
+        self.update_background_playback();
+        window::get_latest().and_then(move |id: window::Id| {
+            window::set_mode(id, window::Mode::Hidden)
+        })
+    }
 
-    //     path
-    // }
+    fn show_window(&mut self) -> Task<Message> {
+        self.window_hidden = false;
+        self.update_background_playback();
+        window::get_latest().and_then(move |id: window::Id| {
+            window::set_mode::<Message>(id, window::Mode::Windowed)
+                .chain(window::minimize(id, false))
+                .chain(window::gain_focus(id))
+        })
+    }
 
-    // fn load() -> Result<State, LoadError> {
-    //     let contents = read_to_string(Self::path()).map_err(|_| LoadError::File)?;
+    fn get_profile(&self, game: &PossibleGames, name: &str) -> Option<&LaunchProfile> {
+        self.profiles.get(game)?.iter().find(|profile| profile.name == name)
+    }
+
+    fn get_selected_profile(&self, game: &PossibleGames) -> Option<&LaunchProfile> {
+        self.get_profile(game, self.selected_profiles.get(game)?)
+    }
 
-    //     let saved_state: SavedState =
-    //         serde_json::from_str(&contents).map_err(|_| LoadError::Format)?;
+    /// Host and port of the server selected for a game. The local server has to be installed but isn't started here.
+    fn get_server_target(&self, game: &PossibleGames) -> Result<Option<(String, u16)>, SupervisorError> {
+        match self.selected_servers.get(game) {
+            Some(ServerChoice::Local) => {
+                if !self.server_installs.contains_key(game) {
+                    return Err(SupervisorError::NotInstalled(game.clone(), ProcessKind::Server));
+                }
+                Ok(Some(("127.0.0.1".to_string(), get_game_definition(game).default_server_port)))
+            },
+            Some(ServerChoice::Remote(address)) => Ok(self.remote_servers.iter()
+                .find(|server| server.game == *game && server.address() == *address)
+                .map(|server| (server.host.clone(), server.port))),
+            None => Ok(None),
+        }
+    }
+
+    /// Works out what to start before any hook runs, nothing is started yet.
+    fn plan_launch(&self, game: PossibleGames, profile: Option<LaunchProfile>) -> Result<LaunchPlan, SupervisorError> {
+        let install_dir = profile.as_ref()
+            .and_then(|profile| profile.client_install.clone())
+            .or_else(|| self.game_installs.get(&game).cloned())
+            .ok_or_else(|| SupervisorError::NotInstalled(game.clone(), ProcessKind::Client))?;
+        // a server target in the profile wins over the one picked in the server list
+        let (server, local_server) = match profile.as_ref().and_then(|profile| profile.server_host.clone()) {
+            Some(host) => (Some((host, profile.as_ref().and_then(|profile| profile.server_port).unwrap_or(get_game_definition(&game).default_server_port))), false),
+            None => (self.get_server_target(&game)?, matches!(self.selected_servers.get(&game), Some(ServerChoice::Local))),
+        };
+        Ok(LaunchPlan { game, profile, install_dir, server, local_server })
+    }
+
+    /// Hooks of a launch in the order they run: the game's pre-launch hook before the profile's, the other way around after exit.
+    fn get_hooks(&self, plan: &LaunchPlan, stage: HookStage) -> Vec<Hook> {
+        let game_hooks = self.game_hooks.get(&plan.game);
+        match stage {
+            HookStage::PreLaunch => [game_hooks.and_then(|hooks| hooks.pre_launch.clone()), plan.profile.as_ref().and_then(|profile| profile.pre_launch_hook.clone())],
+            HookStage::PostExit => [plan.profile.as_ref().and_then(|profile| profile.post_exit_hook.clone()), game_hooks.and_then(|hooks| hooks.post_exit.clone())],
+        }.into_iter().flatten().collect()
+    }
+
+    fn launch_game(&mut self, game: PossibleGames, profile: Option<LaunchProfile>) -> Task<Message> {
+        let plan = match self.plan_launch(game, profile) {
+            Ok(plan) => plan,
+            Err(err) => {
+                self.status_message = Some(err.to_string());
+                self.update_tray();
+                return Task::none();
+            },
+        };
 
-    //     Ok(State {
-    //         selected_game: PossibleGames::WutheringWaves,
-    //         installed_games: saved_state.installed_games,
-    //         installed_game_servers: saved_state.installed_game_servers,
-    //         db_software_installed: saved_state.db_software_installed,
-    //     })
-    // }
+        let hooks = self.get_hooks(&plan, HookStage::PreLaunch);
+        if hooks.is_empty() {
+            return self.spawn_client(plan);
+        }
+        self.status_message = Some("Running pre-launch hooks...".to_string());
+        let env = plan.get_hook_env();
+        Task::perform(run_hooks(hooks, HookStage::PreLaunch, env), move |result| Message::PreLaunchHooksFinished(plan.clone(), result))
+    }
 
-    // async fn save(self) -> Result<(), SaveError> {
-    //     let saved_state = SavedState {
-    //         installed_games: self.installed_games,
-    //         installed_game_servers: self.installed_game_servers,
-    //         db_software_installed: self.db_software_installed,
-    //     };
+    fn spawn_client(&mut self, plan: LaunchPlan) -> Task<Message> {
+        // started only now so a failed pre-launch hook doesn't leave it running
+        if plan.local_server && !self.supervisor.is_running(&plan.game, ProcessKind::Server) && let Err(err) = self.spawn_server(&plan.game) {
+            self.status_message = Some(err.to_string());
+            self.update_tray();
+            return Task::none();
+        }
+        let mut command = get_client_command(&plan.game, &plan.install_dir);
+        if let Some((host, port)) = &plan.server {
+            command.env("RR_SERVER_HOST", host).env("RR_SERVER_PORT", port.to_string());
+        }
+        if let Some(profile) = &plan.profile {
+            profile.apply(&mut command);
+        }
+        // the clients are windows builds, elsewhere they go through wine or proton
+        if cfg!(target_os = "linux")
+            && let Some(settings) = self.runner_settings.get(&plan.game)
+            && let Some(runner) = &settings.runner
+            && let Some(prefix) = get_prefix_dir(&plan.game)
+        {
+            command = wrap_command(&command, runner, settings, &prefix);
+        }
+        let result = self.supervisor.spawn(&plan.game, ProcessKind::Client, command);
+        if result.is_ok() {
+            self.active_launches.insert(plan.game.clone(), plan);
+        }
+        self.status_message = result.err().map(|err| err.to_string());
+        self.update_tray();
 
-    //     let json = serde_json::to_string_pretty(&saved_state).map_err(|_| SaveError::Format)?;
+        if self.status_message.is_none() && self.minimize_to_tray_on_launch && self.tray.is_some() {
+            return self.hide_to_tray();
+        }
+        Task::none()
+    }
 
-    //     let path = Self::path();
+    fn spawn_server(&mut self, game: &PossibleGames) -> Result<(), SupervisorError> {
+        match self.server_installs.get(game) {
+            Some(server_dir) => self.supervisor.spawn(game, ProcessKind::Server, get_server_command(game, server_dir)),
+            None => Err(SupervisorError::NotInstalled(game.clone(), ProcessKind::Server)),
+        }
+    }
 
-    //     if let Some(dir) = path.parent() {
-    //         create_dir_all(dir).map_err(|_| SaveError::Write)?;
-    //     }
+    fn start_server(&mut self, game: PossibleGames) {
+        self.status_message = self.spawn_server(&game).err().map(|err| err.to_string());
+        self.update_tray();
+    }
 
-    //     {
-    //         fs::write(path, json.as_bytes()).map_err(|_| SaveError::Write)?;
-    //     }
+    fn stop_server(&mut self, game: PossibleGames) {
+        self.supervisor.stop(&game, ProcessKind::Server);
+        self.update_tray();
+    }
 
-    //     sleep(std::time::Duration::from_secs(2));
+    /// Handles the arguments of a new invocation, forwarded to this instance or given at startup.
+    fn handle_command(&mut self, args: Vec<String>) -> Task<Message> {
+        let focus = self.show_window();
+        match parse_launcher_command(&args) {
+            Ok(None) => focus,
+            Ok(Some(LauncherCommand::Launch(game))) => focus.chain(Task::done(Message::LaunchGame(game))),
+            Ok(Some(LauncherCommand::DeepLink(url))) => focus.chain(self.handle_deep_link(&url)),
+            Err(err) => {
+                self.status_message = Some(err);
+                focus
+            },
+        }
+    }
 
-    //     Ok(())
-    // }
+    /// Whether launching `game` would also start the local server or run hooks.
+    fn launch_has_side_effects(&self, game: &PossibleGames, profile: Option<&str>) -> bool {
+        let profile = match profile {
+            Some(name) => self.get_profile(game, name),
+            None => self.get_selected_profile(game),
+        };
+        // mirrors plan_launch, a server in the profile wins over the selected one
+        let starts_server = profile.is_none_or(|profile| profile.server_host.is_none())
+            && matches!(self.selected_servers.get(game), Some(ServerChoice::Local))
+            && !self.supervisor.is_running(game, ProcessKind::Server);
+        let game_hooks = self.game_hooks.get(game).is_some_and(|hooks| hooks.pre_launch.is_some() || hooks.post_exit.is_some());
+        let profile_hooks = profile.is_some_and(|profile| profile.pre_launch_hook.is_some() || profile.post_exit_hook.is_some());
+        starts_server || game_hooks || profile_hooks
+    }
+
+    fn handle_deep_link(&mut self, url: &url::Url) -> Task<Message> {
+        match parse_deep_link(url) {
+            Ok(action) => {
+                let launch_side_effects = match &action {
+                    DeepLinkAction::Launch { game, profile } => self.launch_has_side_effects(game, profile.as_deref()),
+                    DeepLinkAction::AddServer(_) => false,
+                };
+                if !action.modifies_state(launch_side_effects) {
+                    return self.run_deep_link(action);
+                }
+                self.pending_deep_link = Some(action);
+                Task::none()
+            },
+            Err(err) => {
+                self.status_message = Some(err);
+                Task::none()
+            },
+        }
+    }
+
+    fn run_deep_link(&mut self, action: DeepLinkAction) -> Task<Message> {
+        match action {
+            DeepLinkAction::Launch { game, profile: None } => Task::done(Message::LaunchGame(game)),
+            DeepLinkAction::Launch { game, profile: Some(profile) } => Task::done(Message::LaunchGameWithProfile(game, profile)),
+            DeepLinkAction::AddServer(server) => self.add_server(server),
+        }
+    }
+
+    fn check_servers(&self) -> Task<Message> {
+        Task::batch(self.remote_servers.iter().cloned().map(|server| {
+            let address = server.address();
+            Task::perform(check_server(server), move |status| Message::ServerChecked(address.clone(), status))
+        }))
+    }
+
+    fn add_server(&mut self, server: RemoteServer) -> Task<Message> {
+        if self.remote_servers.iter().any(|existing| existing.game == server.game && existing.address() == server.address()) {
+            self.status_message = Some(format!("{} is already in the server list", server.address()));
+            return Task::none();
+        }
+        self.status_message = Some(format!("Added the server \"{}\"", server.name));
+        let address = server.address();
+        self.remote_servers.push(server.clone());
+        self.save();
+        Task::perform(check_server(server), move |status| Message::ServerChecked(address.clone(), status))
+    }
+
+    fn quit(&mut self) -> Task<Message> {
+        self.save();
+        self.supervisor.stop_servers();
+        iced::exit()
+    }
 }
 
 fn rad(deg: f32) -> f32 {
@@ -191,19 +757,24 @@ fn style_container(direction: f32, use_gradient: bool) -> container::Style {
     }
 }
 impl Launcher {
-    fn boot() -> (Self, Task<Message>) {
-        let launcher_bg = get_game_background(&State::default());
+    fn boot(saved_state: SavedState, args: Vec<String>) -> (Self, Task<Message>) {
         let mut icons = HashMap::new();
         for game in PossibleGames::iter() {
             let icon = get_game_icon_handle(&game);
             icons.insert(game, icon);
         }
-        let final_state = State {
-            background: Some(launcher_bg),
-            icon_images: icons,
-            ..State::default()
-        };
-        (Self::Loaded(Box::new(final_state)), Task::none())
+        let mut final_state: Box<State> = saved_state.into();
+        final_state.background = Some(get_game_background(&final_state));
+        final_state.icon_images = icons;
+        final_state.sessions = load_sessions();
+
+        final_state.tray = spawn_tray(final_state.get_tray_menu_state());
+        final_state.tray_menu = final_state.get_tray_menu_state();
+
+        let low_power = final_state.apply_low_power();
+        let monitor = get_monitor_info().map(Message::MonitorChecked);
+        let command = if args.is_empty() {Task::none()} else {Task::done(Message::InstanceCommand(args))};
+        (Self::Loaded(final_state), Task::batch([low_power, monitor, command]))
     }
 
     fn title(&self) -> String {
@@ -221,7 +792,7 @@ impl Launcher {
                 },
                 _ => Task::none(),
             },
-            Launcher::Loaded(_) => {
+            Launcher::Loaded(state) => {
                 match message {
                     Message::DragStarted => {
                         window::get_latest().and_then(move |id: window::Id| {
@@ -229,32 +800,850 @@ impl Launcher {
                         })
                     },
                     Message::Close => {
+                        if state.minimize_to_tray_on_close && state.tray.is_some() {
+                            return state.hide_to_tray();
+                        }
+                        state.quit()
+                    },
+                    Message::ToggleMaximize => {
+                        state.window.maximized = !state.window.maximized;
+                        let maximized = state.window.maximized;
+                        window::get_latest().and_then(move |id: window::Id| {
+                            window::maximize(id, maximized)
+                        })
+                    },
+                    Message::ResizeStarted(direction) => {
                         window::get_latest().and_then(move |id: window::Id| {
-                            window::close(id)
+                            window::drag_resize(id, direction)
                         })
                     },
                     Message::Minimize => {
+                        state.window_hidden = true;
+                        state.update_background_playback();
                         window::get_latest().and_then(move |id: window::Id| {
                             window::minimize(id, true)
                         })
-                    }
+                    },
+                    Message::ToggleDebugOverlay => {
+                        state.show_debug_overlay = !state.show_debug_overlay;
+                        Task::none()
+                    },
+                    Message::WindowEvent(event) => {
+                        match event {
+                            // minimized windows report a zero size on windows
+                            window::Event::Resized(size) => {
+                                state.window_hidden = size.width == 0.0 || size.height == 0.0;
+                                if !state.window_hidden {
+                                    // the os can maximize the window too, e.g. snapping it to the top of the screen
+                                    return window::get_latest().and_then(window::is_maximized)
+                                        .map(move |maximized| Message::WindowResized(size, maximized));
+                                }
+                            },
+                            // and are moved far off screen
+                            window::Event::Moved(point) if point.x > -30000.0 && point.y > -30000.0 => {
+                                let maximized = window::get_latest().and_then(window::is_maximized)
+                                    .map(move |maximized| Message::WindowMoved(point, maximized));
+                                return Task::batch([maximized, get_monitor_info().map(Message::MonitorChanged)]);
+                            },
+                            window::Event::Focused => state.window_hidden = false,
+                            window::Event::CloseRequested => {
+                                if state.minimize_to_tray_on_close && state.tray.is_some() {
+                                    return state.hide_to_tray();
+                                }
+                                return state.quit();
+                            },
+                            _ => {}
+                        }
+                        state.update_background_playback();
+                        Task::none()
+                    },
+                    Message::PollGameProcesses => {
+                        Task::perform(poll_running_games(), Message::GameProcessesPolled)
+                    },
+                    Message::GameProcessesPolled(running_games) => {
+                        state.running_games = running_games;
+                        let mut exit_tasks = Vec::new();
+                        let (exits, restart_errors) = state.supervisor.poll();
+                        if let Some(err) = restart_errors.last() {
+                            state.status_message = Some(err.to_string());
+                        }
+                        for exit in exits {
+                            if exit.kind == ProcessKind::Server && exit.is_crash() {
+                                let code = exit.status.and_then(|status| status.code()).map_or("a signal".to_string(), |code| format!("code {code}"));
+                                state.status_message = Some(format!("The {} server stopped with {code}", get_game_definition(&exit.game).display_name));
+                            }
+                            let plan = match exit.kind {
+                                ProcessKind::Client => state.active_launches.remove(&exit.game),
+                                ProcessKind::Server => None,
+                            };
+                            let session = Session::from_exit(&exit, plan.as_ref().and_then(|plan| plan.profile.as_ref()).map(|profile| profile.name.clone()));
+                            if let Err(err) = record_session(&session) {
+                                tracing::error!("Failed to record the session: {err}");
+                            }
+                            state.sessions.push(session);
+                            if exit.is_crash() {
+                                let install_dir = match exit.kind {
+                                    ProcessKind::Client => plan.as_ref().map(|plan| plan.install_dir.clone()),
+                                    ProcessKind::Server => state.server_installs.get(&exit.game).cloned(),
+                                };
+                                let output = state.supervisor.output(&exit.game, exit.kind)
+                                    .and_then(|log| log.lock().ok().map(|log| log.tail(CRASH_OUTPUT_LINES)))
+                                    .unwrap_or_default();
+                                let info = CrashInfo {
+                                    game: exit.game.clone(),
+                                    kind: exit.kind,
+                                    started: exit.started,
+                                    ended: exit.ended,
+                                    status: exit.status,
+                                    profile: plan.as_ref().and_then(|plan| plan.profile.as_ref()).map(|profile| profile.name.clone()),
+                                    output,
+                                    install_dir,
+                                };
+                                exit_tasks.push(Task::perform(create_crash_report(info), Message::CrashReportCreated));
+                            }
+                            if let Some(plan) = plan {
+                                let hooks = state.get_hooks(&plan, HookStage::PostExit);
+                                if !hooks.is_empty() {
+                                    let mut env = plan.get_hook_env();
+                                    env.push(("RR_EXIT_CODE".to_string(), exit.status.and_then(|status| status.code()).map(|code| code.to_string()).unwrap_or_default()));
+                                    exit_tasks.push(Task::perform(run_hooks(hooks, HookStage::PostExit, env), Message::PostExitHooksFinished));
+                                }
+                            }
+                        }
+                        state.update_background_playback();
+                        state.update_tray();
+                        Task::batch(exit_tasks)
+                    },
+                    Message::LaunchGame(game) => {
+                        let profile = state.get_selected_profile(&game).cloned();
+                        state.launch_game(game, profile)
+                    },
+                    Message::LaunchGameWithProfile(game, name) => match state.get_profile(&game, &name).cloned() {
+                        Some(profile) => state.launch_game(game, Some(profile)),
+                        None => {
+                            state.status_message = Some(format!("No \"{name}\" profile for {}", get_game_definition(&game).display_name));
+                            Task::none()
+                        },
+                    },
+                    Message::OpenServerList => {
+                        state.server_list = Some(ServerForm::default());
+                        state.check_servers()
+                    },
+                    Message::CloseServerList => {
+                        state.server_list = None;
+                        Task::none()
+                    },
+                    Message::CheckServers => state.check_servers(),
+                    Message::ServerChecked(address, status) => {
+                        state.server_statuses.insert(address, status);
+                        Task::none()
+                    },
+                    Message::SelectServer(choice) => {
+                        state.selected_servers.insert(state.selected_game.clone(), choice);
+                        state.save();
+                        Task::none()
+                    },
+                    Message::RemoveServer(address) => {
+                        let game = state.selected_game.clone();
+                        state.remote_servers.retain(|server| server.game != game || server.address() != address);
+                        if state.selected_servers.get(&game) == Some(&ServerChoice::Remote(address.clone())) {
+                            state.selected_servers.remove(&game);
+                        }
+                        if !state.remote_servers.iter().any(|server| server.address() == address) {
+                            state.server_statuses.remove(&address);
+                        }
+                        state.save();
+                        Task::none()
+                    },
+                    Message::ServerFormName(name) => {
+                        if let Some(form) = state.server_list.as_mut() {
+                            form.name = name;
+                        }
+                        Task::none()
+                    },
+                    Message::ServerFormHost(host) => {
+                        if let Some(form) = state.server_list.as_mut() {
+                            form.host = host;
+                        }
+                        Task::none()
+                    },
+                    Message::ServerFormPort(port) => {
+                        if let Some(form) = state.server_list.as_mut() && port.chars().all(|c| c.is_ascii_digit()) {
+                            form.port = port;
+                        }
+                        Task::none()
+                    },
+                    Message::ServerFormRegion(region) => {
+                        if let Some(form) = state.server_list.as_mut() {
+                            form.region = region;
+                        }
+                        Task::none()
+                    },
+                    Message::ServerFormToggleProtocol => {
+                        if let Some(form) = state.server_list.as_mut() {
+                            form.protocol = match form.protocol {
+                                ServerProtocol::Tcp => ServerProtocol::Udp,
+                                ServerProtocol::Udp => ServerProtocol::Tcp,
+                            };
+                        }
+                        Task::none()
+                    },
+                    Message::AddServer => {
+                        let Some(form) = state.server_list.as_ref() else {
+                            return Task::none();
+                        };
+                        match form.to_server(&state.selected_game) {
+                            Ok(server) => {
+                                state.server_list = Some(ServerForm { protocol: form.protocol, ..ServerForm::default() });
+                                state.add_server(server)
+                            },
+                            Err(err) => {
+                                state.status_message = Some(err);
+                                Task::none()
+                            },
+                        }
+                    },
+                    Message::OpenServerConfig(game) => {
+                        match state.server_installs.get(&game).map(|server_dir| ConfigEditor::open(&game, server_dir)) {
+                            Some(Ok(editor)) => state.config_editor = Some(editor),
+                            Some(Err(err)) => state.status_message = Some(err),
+                            None => state.status_message = Some(SupervisorError::NotInstalled(game, ProcessKind::Server).to_string()),
+                        }
+                        Task::none()
+                    },
+                    Message::CloseServerConfig => {
+                        state.config_editor = None;
+                        Task::none()
+                    },
+                    Message::ConfigFieldChanged(index, value) => {
+                        if let Some(editor) = state.config_editor.as_mut() {
+                            editor.set_field(index, value);
+                        }
+                        Task::none()
+                    },
+                    Message::ConfigToggleRaw => {
+                        if let Some(editor) = state.config_editor.as_mut() {
+                            state.status_message = editor.toggle_raw().err();
+                        }
+                        Task::none()
+                    },
+                    Message::ConfigRawEdited(action) => {
+                        if let Some(editor) = state.config_editor.as_mut() {
+                            editor.edit_raw(action);
+                        }
+                        Task::none()
+                    },
+                    Message::SaveServerConfig => {
+                        let Some(editor) = state.config_editor.as_mut() else {
+                            return Task::none();
+                        };
+                        let game = editor.game.clone();
+                        state.status_message = Some(match editor.save() {
+                            Ok(backup) => {
+                                let backup = backup.map(|backup| format!(", the previous one is at {}", backup.display())).unwrap_or_default();
+                                format!("Saved the {} server config{backup}", get_game_definition(&game).display_name)
+                            },
+                            Err(err) => err,
+                        });
+
+                        // the server only reads its config on start, the next poll starts it again once the old one exited
+                        if state.supervisor.is_running(&game, ProcessKind::Server) && let Some(server_dir) = state.server_installs.get(&game) {
+                            if let Err(err) = state.supervisor.restart(&game, ProcessKind::Server, get_server_command(&game, server_dir)) {
+                                state.status_message = Some(err.to_string());
+                            }
+                            state.update_tray();
+                        }
+                        Task::none()
+                    },
+                    Message::OpenServerLog(game) => {
+                        let mut viewer = LogViewer::new(game.clone());
+                        if let Some(log) = state.supervisor.output(&game, ProcessKind::Server) {
+                            viewer.refresh(&log);
+                        }
+                        state.server_log = Some(viewer);
+                        Task::none()
+                    },
+                    Message::CloseServerLog => {
+                        state.server_log = None;
+                        Task::none()
+                    },
+                    Message::RefreshServerLog => {
+                        if let Some(viewer) = state.server_log.as_mut() && let Some(log) = state.supervisor.output(&viewer.game, ProcessKind::Server) {
+                            viewer.refresh(&log);
+                        }
+                        Task::none()
+                    },
+                    Message::ToggleServerLogFollow => {
+                        if let Some(viewer) = state.server_log.as_mut() {
+                            viewer.follow = !viewer.follow;
+                            if let Some(log) = state.supervisor.output(&viewer.game, ProcessKind::Server) {
+                                viewer.refresh(&log);
+                            }
+                        }
+                        Task::none()
+                    },
+                    Message::ServerLogFilterChanged(filter) => {
+                        if let Some(viewer) = state.server_log.as_mut() {
+                            viewer.set_filter(filter);
+                        }
+                        Task::none()
+                    },
+                    Message::ServerLogSearchChanged(search) => {
+                        if let Some(viewer) = state.server_log.as_mut() {
+                            viewer.set_search(search);
+                        }
+                        Task::none()
+                    },
+                    Message::ServerLogLevelChanged(level) => {
+                        if let Some(viewer) = state.server_log.as_mut() {
+                            viewer.min_level = level;
+                        }
+                        Task::none()
+                    },
+                    Message::OpenLogFolder => {
+                        let result = get_log_dir().ok_or_else(|| "no data directory".to_string())
+                            .and_then(|dir| open_in_file_manager(&dir).map_err(|err| err.to_string()));
+                        if let Err(err) = result {
+                            state.status_message = Some(format!("Failed to open the log folder: {err}"));
+                        }
+                        Task::none()
+                    },
+                    Message::PreLaunchHooksFinished(plan, result) => match result {
+                        Ok(()) => state.spawn_client(plan),
+                        Err(err) => {
+                            tracing::warn!("Aborted launching {}: {err}", get_game_definition(&plan.game).display_name);
+                            state.status_message = Some(format!("Launch aborted, a pre-launch hook failed: {err}"));
+                            Task::none()
+                        },
+                    },
+                    Message::PostExitHooksFinished(result) => {
+                        if let Err(err) = result {
+                            state.status_message = Some(format!("A post-exit hook failed: {err}"));
+                        }
+                        Task::none()
+                    },
+                    Message::OpenRunnerSettings => {
+                        let settings = state.runner_settings.get(&state.selected_game).cloned().unwrap_or_default();
+                        state.runner_panel = Some(RunnerPanel::new(state.selected_game.clone(), &settings));
+                        Task::none()
+                    },
+                    Message::CloseRunnerSettings => {
+                        state.runner_panel = None;
+                        Task::none()
+                    },
+                    Message::RunnerSelected(choice) => {
+                        let Some(panel) = state.runner_panel.as_ref() else {
+                            return Task::none();
+                        };
+                        state.runner_settings.entry(panel.game.clone()).or_default().runner = match choice {
+                            RunnerChoice::Native => None,
+                            RunnerChoice::Runner(runner) => Some(runner),
+                        };
+                        state.save();
+                        Task::none()
+                    },
+                    Message::ToggleDxvk(dxvk) => {
+                        let Some(panel) = state.runner_panel.as_ref() else {
+                            return Task::none();
+                        };
+                        state.runner_settings.entry(panel.game.clone()).or_default().dxvk = dxvk;
+                        state.save();
+                        Task::none()
+                    },
+                    Message::DllOverridesChanged(text) => {
+                        let Some(panel) = state.runner_panel.as_mut() else {
+                            return Task::none();
+                        };
+                        let parsed = parse_dll_overrides(&text);
+                        panel.dll_overrides = text;
+                        panel.error = parsed.as_ref().err().cloned();
+                        if let Ok(dll_overrides) = parsed {
+                            state.runner_settings.entry(panel.game.clone()).or_default().dll_overrides = dll_overrides;
+                            state.save();
+                        }
+                        Task::none()
+                    },
+                    Message::RunnerEnvChanged(text) => {
+                        let Some(panel) = state.runner_panel.as_mut() else {
+                            return Task::none();
+                        };
+                        let parsed = parse_env(&text);
+                        panel.env = text;
+                        panel.error = parsed.as_ref().err().cloned();
+                        if let Ok(env) = parsed {
+                            state.runner_settings.entry(panel.game.clone()).or_default().env = env;
+                            state.save();
+                        }
+                        Task::none()
+                    },
+                    Message::PreparePrefix(reset) => {
+                        let Some(panel) = state.runner_panel.as_mut() else {
+                            return Task::none();
+                        };
+                        let runner = state.runner_settings.get(&panel.game).and_then(|settings| settings.runner.clone());
+                        let (Some(runner), Some(prefix)) = (runner, get_prefix_dir(&panel.game)) else {
+                            return Task::none();
+                        };
+                        panel.busy = true;
+                        panel.error = None;
+                        Task::perform(prepare_prefix(runner, prefix, reset), Message::PrefixPrepared)
+                    },
+                    Message::PrefixPrepared(result) => {
+                        if let Some(panel) = state.runner_panel.as_mut() {
+                            panel.busy = false;
+                            panel.error = result.err().map(|err| format!("Failed to prepare the prefix: {err}"));
+                        }
+                        Task::none()
+                    },
+                    Message::OpenSessions => {
+                        state.show_sessions = true;
+                        Task::none()
+                    },
+                    Message::CloseSessions => {
+                        state.show_sessions = false;
+                        Task::none()
+                    },
+                    Message::ExportSessions(format) => {
+                        state.status_message = Some(match export_sessions(&state.sessions, format) {
+                            Ok(path) => format!("Exported the sessions to {}", path.display()),
+                            Err(err) => format!("Failed to export the sessions: {err}"),
+                        });
+                        Task::none()
+                    },
+                    Message::CrashReportCreated(result) => {
+                        match result {
+                            Ok(report) => state.crash_report = Some(report),
+                            Err(err) => state.status_message = Some(err),
+                        }
+                        Task::none()
+                    },
+                    Message::DismissCrashReport => {
+                        state.crash_report = None;
+                        Task::none()
+                    },
+                    Message::OpenCrashReportFolder => {
+                        let result = get_crash_report_dir().ok_or_else(|| "no data directory".to_string())
+                            .and_then(|dir| open_in_file_manager(&dir).map_err(|err| err.to_string()));
+                        if let Err(err) = result {
+                            state.status_message = Some(format!("Failed to open the crash report folder: {err}"));
+                        }
+                        state.crash_report = None;
+                        Task::none()
+                    },
+                    Message::ToggleDiagnostics => {
+                        state.diagnostics = match state.diagnostics {
+                            Some(_) => None,
+                            None => Some(Diagnostics {
+                                lines: get_recent_log_lines(),
+                                filter: state.log_filter.clone().unwrap_or_default(),
+                                error: None,
+                            }),
+                        };
+                        Task::none()
+                    },
+                    Message::RefreshDiagnostics => {
+                        if let Some(diagnostics) = state.diagnostics.as_mut() {
+                            diagnostics.lines = get_recent_log_lines();
+                        }
+                        Task::none()
+                    },
+                    Message::LogFilterChanged(filter) => {
+                        if let Some(diagnostics) = state.diagnostics.as_mut() {
+                            diagnostics.filter = filter;
+                        }
+                        Task::none()
+                    },
+                    Message::ApplyLogFilter => {
+                        let Some(diagnostics) = state.diagnostics.as_mut() else {
+                            return Task::none();
+                        };
+                        let filter = diagnostics.filter.trim().to_string();
+                        diagnostics.error = set_log_filter(if filter.is_empty() {DEFAULT_LOG_FILTER} else {&filter}).err();
+                        if diagnostics.error.is_none() {
+                            tracing::info!("Log filter set to {}", if filter.is_empty() {DEFAULT_LOG_FILTER} else {&filter});
+                            state.log_filter = (!filter.is_empty()).then_some(filter);
+                            state.save();
+                        }
+                        Task::none()
+                    },
+                    Message::OpenSettings => {
+                        let game = state.selected_game.clone();
+                        state.settings_panel = Some(SettingsPanel::new(game.clone(), state.game_installs.get(&game), state.server_installs.get(&game), state.game_hooks.get(&game)));
+                        Task::none()
+                    },
+                    Message::CloseSettings => {
+                        state.settings_panel = None;
+                        Task::none()
+                    },
+                    Message::GameInstallChanged(path) => {
+                        let Some(panel) = state.settings_panel.as_mut() else {
+                            return Task::none();
+                        };
+                        let parsed = parse_install_dir(&panel.game, ProcessKind::Client, &path);
+                        panel.game_install = path;
+                        panel.error = parsed.as_ref().err().cloned();
+                        match parsed {
+                            Ok(Some(dir)) => state.game_installs.insert(panel.game.clone(), dir),
+                            Ok(None) => state.game_installs.remove(&panel.game),
+                            Err(_) => return Task::none(),
+                        };
+                        state.save();
+                        Task::none()
+                    },
+                    Message::ServerInstallChanged(path) => {
+                        let Some(panel) = state.settings_panel.as_mut() else {
+                            return Task::none();
+                        };
+                        let parsed = parse_install_dir(&panel.game, ProcessKind::Server, &path);
+                        panel.server_install = path;
+                        panel.error = parsed.as_ref().err().cloned();
+                        match parsed {
+                            Ok(Some(dir)) => state.server_installs.insert(panel.game.clone(), dir),
+                            Ok(None) => state.server_installs.remove(&panel.game),
+                            Err(_) => return Task::none(),
+                        };
+                        state.save();
+                        state.update_tray();
+                        Task::none()
+                    },
+                    Message::HookCommandChanged(stage, command) => {
+                        if let Some(panel) = state.settings_panel.as_mut() {
+                            panel.hook_fields(stage).command = command;
+                        }
+                        state.save_game_hook(stage);
+                        Task::none()
+                    },
+                    Message::HookTimeoutChanged(stage, timeout) => {
+                        if let Some(panel) = state.settings_panel.as_mut() && timeout.chars().all(|c| c.is_ascii_digit()) {
+                            panel.hook_fields(stage).timeout = timeout;
+                        }
+                        state.save_game_hook(stage);
+                        Task::none()
+                    },
+                    Message::HookAbortToggled(abort_on_failure) => {
+                        if let Some(panel) = state.settings_panel.as_mut() {
+                            panel.abort_on_failure = abort_on_failure;
+                        }
+                        state.save_game_hook(HookStage::PreLaunch);
+                        Task::none()
+                    },
+                    Message::LanguageSelected(choice) => {
+                        state.language = match choice {
+                            LanguageChoice::System => None,
+                            LanguageChoice::Language(code) => Some(code.to_string()),
+                        };
+                        state.save();
+                        if let Some(LauncherBackground::Video(video, _)) = state.background.as_mut() {
+                            select_preferred_tracks(video, state.language.as_deref());
+                        }
+                        Task::none()
+                    },
+                    Message::ProfileSelected(choice) => {
+                        match choice {
+                            ProfileChoice::Default => state.selected_profiles.remove(&state.selected_game),
+                            ProfileChoice::Named(name) => state.selected_profiles.insert(state.selected_game.clone(), name),
+                        };
+                        state.save();
+                        Task::none()
+                    },
+                    Message::ExportProfile => {
+                        if let Some(profile) = state.get_selected_profile(&state.selected_game).cloned() {
+                            state.status_message = Some(match export_profile(&profile) {
+                                Ok(path) => format!("Exported to {}", path.display()),
+                                Err(err) => format!("Failed to export the profile: {err}"),
+                            });
+                        }
+                        Task::none()
+                    },
+                    Message::ImportProfiles => {
+                        let game = state.selected_game.clone();
+                        let imported = import_profiles(&game);
+                        let profiles = state.profiles.entry(game.clone()).or_default();
+                        for profile in &imported {
+                            match profiles.iter_mut().find(|existing| existing.name == profile.name) {
+                                Some(existing) => *existing = profile.clone(),
+                                None => profiles.push(profile.clone()),
+                            }
+                        }
+                        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+                        let dir = get_profiles_dir(&game).map_or_else(|| "the profiles folder".to_string(), |dir| dir.display().to_string());
+                        state.status_message = Some(format!("Imported {} profiles from {dir}", imported.len()));
+                        state.save();
+                        Task::none()
+                    },
+                    Message::StartServer(game) => {
+                        state.start_server(game);
+                        Task::none()
+                    },
+                    Message::StopServer(game) => {
+                        state.stop_server(game);
+                        Task::none()
+                    },
+                    Message::PollInstanceCommands => {
+                        Task::batch(get_forwarded_commands().into_iter().map(|args| Task::done(Message::InstanceCommand(args))))
+                    },
+                    Message::InstanceCommand(args) => state.handle_command(args),
+                    Message::ConfirmDeepLink => match state.pending_deep_link.take() {
+                        Some(action) => state.run_deep_link(action),
+                        None => Task::none(),
+                    },
+                    Message::CancelDeepLink => {
+                        state.pending_deep_link = None;
+                        Task::none()
+                    },
+                    Message::PollTray => {
+                        Task::batch(get_tray_actions().into_iter().map(|action| Task::done(Message::TrayAction(action))))
+                    },
+                    Message::TrayAction(action) => match action {
+                        TrayAction::Show => state.show_window(),
+                        TrayAction::Launch(game) => Task::done(Message::LaunchGame(game)),
+                        TrayAction::StartServer(game) => {
+                            state.start_server(game);
+                            Task::none()
+                        },
+                        TrayAction::StopServer(game) => {
+                            state.stop_server(game);
+                            Task::none()
+                        },
+                        TrayAction::ToggleMinimizeOnLaunch => {
+                            state.minimize_to_tray_on_launch = !state.minimize_to_tray_on_launch;
+                            state.update_tray();
+                            state.save();
+                            Task::none()
+                        },
+                        TrayAction::ToggleMinimizeOnClose => {
+                            state.minimize_to_tray_on_close = !state.minimize_to_tray_on_close;
+                            state.update_tray();
+                            state.save();
+                            Task::none()
+                        },
+                        TrayAction::Quit => state.quit(),
+                    },
+                    Message::ToggleLowPower => {
+                        state.low_power = !state.low_power;
+                        state.save();
+                        state.apply_low_power()
+                    },
+                    Message::LowPowerStillReady(still) => {
+                        // low power may have been turned off again while the still was decoding
+                        if let Some(still) = still {
+                            if state.low_power && matches!(state.background, Some(LauncherBackground::Video(..))) {
+                                state.background = Some(LauncherBackground::Image(still));
+                            }
+                        }
+                        Task::none()
+                    },
+                    Message::VideoStreamsChanged => {
+                        if let Some(LauncherBackground::Video(video, _)) = state.background.as_mut() {
+                            select_preferred_tracks(video, state.language.as_deref());
+                        }
+                        Task::none()
+                    },
+                    Message::BackgroundVideoEvent(VideoEvent::Error(_)) => {
+                        // the player already tried to rebuild the pipeline, keep the last frame as a still
+                        if let Some(LauncherBackground::Video(video, _)) = state.background.as_ref() {
+                            if video.failed() {
+                                state.background = Some(video.current_frame()
+                                    .map(LauncherBackground::Image)
+                                    .unwrap_or_else(get_placeholder_background));
+                            }
+                        }
+                        Task::none()
+                    },
+                    Message::WindowResized(size, maximized) => {
+                        state.window.maximized = maximized;
+                        if !maximized {
+                            state.window.width = size.width;
+                            state.window.height = size.height;
+                        }
+                        Task::none()
+                    },
+                    Message::WindowMoved(point, maximized) => {
+                        state.window.maximized = maximized;
+                        if !maximized {
+                            state.window.position = Some((point.x, point.y));
+                        }
+                        Task::none()
+                    },
+                    Message::MonitorChecked(monitor) => {
+                        if state.window.maximized || state.window.position.is_none() {
+                            state.window.monitor = monitor.or(state.window.monitor);
+                            return Task::none();
+                        }
+
+                        // the saved position belongs to a monitor that is gone or changed, don't restore off screen
+                        let position = match monitor {
+                            Some(monitor) if state.window.monitor.is_some_and(|saved| saved != monitor) => Point::new(
+                                ((monitor.width - state.window.width) / 2.0).max(0.0),
+                                ((monitor.height - state.window.height) / 2.0).max(0.0),
+                            ),
+                            Some(_) => {
+                                state.window.monitor = monitor;
+                                return Task::none();
+                            },
+                            // on no monitor at all, the primary one starts at the origin
+                            None => Point::ORIGIN,
+                        };
+                        state.window.position = Some((position.x, position.y));
+                        window::get_latest()
+                            .and_then(move |id| window::move_to(id, position))
+                            .chain(get_monitor_info().map(Message::MonitorChanged))
+                    },
+                    Message::MonitorChanged(monitor) => {
+                        if monitor.is_some() {
+                            state.window.monitor = monitor;
+                        }
+                        Task::none()
+                    },
+                    Message::OpenGallery => {
+                        let items = get_gallery_items(&state.selected_game);
+                        let thumbnails = load_gallery_thumbnails(&items);
+                        state.gallery = Some(Gallery { game: state.selected_game.clone(), items, thumbnails: HashMap::new(), viewer: None });
+                        state.update_background_playback();
+                        thumbnails
+                    },
+                    Message::GalleryBack => {
+                        // close the viewer first, then the gallery itself
+                        if let Some(gallery) = state.gallery.as_mut() && gallery.viewer.is_some() {
+                            gallery.viewer = None;
+                        } else {
+                            state.gallery = None;
+                            state.update_background_playback();
+                        }
+                        Task::none()
+                    },
+                    Message::GalleryThumbnailReady(path, thumbnail) => {
+                        if let Some(gallery) = state.gallery.as_mut() && let Some(thumbnail) = thumbnail {
+                            gallery.thumbnails.insert(path, thumbnail);
+                        }
+                        Task::none()
+                    },
+                    Message::OpenGalleryItem(index) => {
+                        if let Some(gallery) = state.gallery.as_mut() && let Some(item) = gallery.items.get(index) {
+                            let (viewer, task) = open_gallery_item(item);
+                            gallery.viewer = viewer;
+                            return task;
+                        }
+                        Task::none()
+                    },
+                    Message::GalleryVideoLoaded(path, video) => {
+                        // dropped if the viewer was closed or moved on to another item while loading
+                        if let Some(gallery) = state.gallery.as_mut() && matches!(&gallery.viewer, Some(GalleryViewer::Loading(loading)) if *loading == path) {
+                            gallery.viewer = match video.map(Arc::into_inner) {
+                                Ok(Some(video)) => Some(GalleryViewer::Video { video, subtitle_track: None, subtitle_text: None }),
+                                Ok(None) => None,
+                                Err(err) => {
+                                    tracing::warn!("Failed to open {}: {err}", path.display());
+                                    None
+                                }
+                            };
+                        }
+                        Task::none()
+                    },
+                    Message::GalleryTogglePause => {
+                        if let Some(GalleryViewer::Video { video, .. }) = state.gallery.as_mut().and_then(|gallery| gallery.viewer.as_mut()) {
+                            if video.eos() {
+                                let _ = video.restart_stream();
+                            } else {
+                                video.set_paused(!video.paused());
+                            }
+                        }
+                        Task::none()
+                    },
+                    Message::GalleryScrub(position) => {
+                        if let Some(GalleryViewer::Video { video, .. }) = state.gallery.as_mut().and_then(|gallery| gallery.viewer.as_mut()) {
+                            let _ = video.scrub(position);
+                        }
+                        Task::none()
+                    },
+                    Message::GalleryScrubEnded => {
+                        if let Some(GalleryViewer::Video { video, .. }) = state.gallery.as_mut().and_then(|gallery| gallery.viewer.as_mut()) {
+                            let _ = video.end_scrub();
+                        }
+                        Task::none()
+                    },
+                    Message::GalleryVolumeChanged(volume) => {
+                        if let Some(GalleryViewer::Video { video, .. }) = state.gallery.as_mut().and_then(|gallery| gallery.viewer.as_mut()) {
+                            video.set_volume(volume);
+                        }
+                        Task::none()
+                    },
+                    Message::GalleryCycleSubtitles => {
+                        if let Some(GalleryViewer::Video { video, subtitle_track, .. }) = state.gallery.as_mut().and_then(|gallery| gallery.viewer.as_mut()) {
+                            cycle_subtitles(video, subtitle_track);
+                        }
+                        Task::none()
+                    },
+                    Message::GalleryVideoEnded => {
+                        // keep the last frame up, the play button restarts it from the beginning
+                        if let Some(GalleryViewer::Video { video, .. }) = state.gallery.as_mut().and_then(|gallery| gallery.viewer.as_mut()) {
+                            video.set_paused(true);
+                        }
+                        Task::none()
+                    },
+                    Message::GallerySubtitleText(text) => {
+                        if let Some(GalleryViewer::Video { subtitle_text, .. }) = state.gallery.as_mut().and_then(|gallery| gallery.viewer.as_mut()) {
+                            *subtitle_text = text;
+                        }
+                        Task::none()
+                    },
                     _ => Task::none()
                 }
             }
         }
     }
 
+    fn subscription(&self) -> Subscription<Message> {
+        let hotkeys = keyboard::on_key_press(|key, _modifiers| match key {
+            keyboard::Key::Named(keyboard::key::Named::F3) => Some(Message::ToggleDebugOverlay),
+            keyboard::Key::Named(keyboard::key::Named::F12) => Some(Message::ToggleDiagnostics),
+            keyboard::Key::Named(keyboard::key::Named::Escape) => Some(Message::GalleryBack),
+            _ => None,
+        });
+
+        let window_events = window::events().map(|(_id, event)| Message::WindowEvent(event));
+        let game_processes = time::every(Duration::from_secs(2)).map(|_| Message::PollGameProcesses);
+
+        // other invocations forward their arguments through the single instance socket
+        let instance_commands = time::every(Duration::from_millis(250)).map(|_| Message::PollInstanceCommands);
+
+        let mut subscriptions = vec![hotkeys, window_events, game_processes, instance_commands];
+        if let Launcher::Loaded(state) = self {
+            // the overlay reads live stats, so keep re-running view while it is shown
+            if state.show_debug_overlay {
+                subscriptions.push(time::every(Duration::from_millis(500)).map(|_| Message::RefreshDebugOverlay));
+            }
+            if state.diagnostics.is_some() {
+                subscriptions.push(time::every(Duration::from_millis(500)).map(|_| Message::RefreshDiagnostics));
+            }
+            if state.server_log.as_ref().is_some_and(|viewer| viewer.follow) {
+                subscriptions.push(time::every(Duration::from_millis(250)).map(|_| Message::RefreshServerLog));
+            }
+            // no point in pinging servers nobody is looking at
+            if !state.remote_servers.is_empty() && !state.window_hidden {
+                subscriptions.push(time::every(CHECK_INTERVAL).map(|_| Message::CheckServers));
+            }
+            // menu clicks queue up in a global channel of the tray crate
+            if state.tray.is_some() {
+                subscriptions.push(time::every(Duration::from_millis(200)).map(|_| Message::PollTray));
+            }
+        }
+        Subscription::batch(subscriptions)
+    }
+
     fn view(&self) -> Element<Message> {  
-        println!("rerender triggered");
         match self {
             Launcher::Loading => center(text("Loading...").size(50)).into(),
             Launcher::Loaded(state) => {
-                let game_selector = mouse_area(container(
+                let compact = state.window.width < COMPACT_WIDTH;
+                let (title_size, label_size, icon_size, bar_padding) = if compact {(20, 13, 48.0, 12)} else {(25, 15, 64.0, 20)};
+
+                let game_selector = container(
                     row![
-                        get_game_icon(state, &PossibleGames::WutheringWaves),
-                        get_game_icon(state, &PossibleGames::ZenlessZoneZero),
-                        get_game_icon(state, &PossibleGames::HonkaiStarRail),
-                        get_game_icon(state, &PossibleGames::GenshinImpact),
+                        get_game_icon(state, &PossibleGames::WutheringWaves, icon_size),
+                        get_game_icon(state, &PossibleGames::ZenlessZoneZero, icon_size),
+                        get_game_icon(state, &PossibleGames::HonkaiStarRail, icon_size),
+                        get_game_icon(state, &PossibleGames::GenshinImpact, icon_size),
                     ]
                     .spacing(10),
                 )
@@ -262,28 +1651,70 @@ impl Launcher {
                 .align_y(Top)
                 .align_x(Center)
                 .width(Length::Fill)
-                .style(move |_| style_container(0.0, true)))
-                .on_press(Message::DragStarted);
+                .style(move |_| style_container(0.0, true));
 
-                let topbar = container(
+                let topbar = container(get_title_bar(
+                    text("Reversed Rooms").size(title_size).font(Font::with_name("Montserrat-SemiBold")).into(),
                     row![
-                    text("Reversed Rooms").size(25).font(Font::with_name("Montserrat-SemiBold")),
-                    Space::new(Length::Fill, Length::Fixed(0.0)),
-                    row![
-                        mouse_area(text("\u{E949}").font(Font::with_name("Segoe MDL2 Assets")).size(25))
-                        .on_release(Message::Minimize),
-                        mouse_area(text("\u{E106}").font(Font::with_name("Segoe MDL2 Assets")).size(25)).on_release(Message::Close)
-                    ].spacing(20)
-                ])
+                        mouse_area(text("Gallery").size(label_size))
+                        .on_release(Message::OpenGallery),
+                        mouse_area(text("Servers").size(label_size))
+                        .on_release(Message::OpenServerList),
+                        mouse_area(text("Playtime").size(label_size))
+                        .on_release(Message::OpenSessions),
+                    ]
+                    .push_maybe(cfg!(target_os = "linux").then(|| mouse_area(text("Runner").size(label_size)).on_release(Message::OpenRunnerSettings)))
+                    .push(mouse_area(text(if state.low_power {"Low power: on"} else {"Low power: off"}).size(label_size))
+                        .on_release(Message::ToggleLowPower))
+                    .push(mouse_area(text("Settings").size(label_size))
+                        .on_release(Message::OpenSettings))
+                    .spacing(if compact {12} else {20}).align_y(Center).into(),
+                    state.window.maximized,
+                    if compact {40.0} else {56.0},
+                ))
                 .width(Length::Fill)
-                .style(move |_| style_container(0.0, false))
-                .padding(20);
+                .style(move |_| style_container(0.0, false));
         
-                let bottom_bar = container(row![
-                    text("insert game announcements").size(25).font(Font::with_name("Montserrat SemiBold")).align_y(Bottom),
-                    Space::new(Length::Fill, Length::Fixed(0.0)),
-                    container(mouse_area(button(text("Launch").size(25))
+                let bottom_text = state.status_message.as_deref().unwrap_or("insert game announcements");
+                let mut bottom_row = row![
+                    text(bottom_text).size(title_size).font(Font::with_name("Montserrat SemiBold")).align_y(Bottom).width(Length::Fill),
+                ].spacing(10).align_y(Center);
+                if state.server_installs.contains_key(&state.selected_game) {
+                    let (label, message) = if state.supervisor.is_running(&state.selected_game, ProcessKind::Server) {
+                        ("Stop server", Message::StopServer(state.selected_game.clone()))
+                    } else {
+                        ("Start server", Message::StartServer(state.selected_game.clone()))
+                    };
+                    bottom_row = bottom_row.push(mouse_area(button(text(label).size(label_size))
                         .padding(10)
+                        .on_press(message)
+                        .style(move |_, _| {
+                            button::Style {
+                                text_color: Color::from_rgba8(255, 255, 255, 1.0),
+                                background: Some(Color::from_rgba8(255, 255, 255, 0.2).into()),
+                                border: border::rounded(5),
+                                ..button::Style::default()
+                            }
+                        })).interaction(iced::mouse::Interaction::Pointer));
+                }
+                let profiles = state.profiles.get(&state.selected_game).map(Vec::as_slice).unwrap_or_default();
+                let selected_profile = match state.get_selected_profile(&state.selected_game) {
+                    Some(profile) => ProfileChoice::Named(profile.name.clone()),
+                    None => ProfileChoice::Default,
+                };
+                let mut profile_actions = row![
+                    mouse_area(text("Import").size(label_size)).on_release(Message::ImportProfiles).interaction(iced::mouse::Interaction::Pointer),
+                ].spacing(10);
+                if selected_profile != ProfileChoice::Default {
+                    profile_actions = profile_actions.push(mouse_area(text("Export").size(label_size)).on_release(Message::ExportProfile).interaction(iced::mouse::Interaction::Pointer));
+                }
+                bottom_row = bottom_row.push(profile_actions);
+                bottom_row = bottom_row.push(pick_list(get_profile_choices(profiles), Some(selected_profile), Message::ProfileSelected)
+                    .text_size(label_size)
+                    .padding(10));
+                bottom_row = bottom_row.push(container(mouse_area(button(text("Launch").size(title_size))
+                        .padding(10)
+                        .on_press(Message::LaunchGame(state.selected_game.clone()))
                         .style(move |_, _| {
                             button::Style {
                                 text_color: Color::from_rgba8(0, 0, 0, 1.0),
@@ -291,12 +1722,13 @@ impl Launcher {
                                 border: border::rounded(5),
                                 ..button::Style::default()
                             }
-                        })).interaction(iced::mouse::Interaction::Pointer))
-                ])
+                        })).interaction(iced::mouse::Interaction::Pointer)));
+
+                let bottom_bar = container(bottom_row)
                 .align_y(Bottom)
                 .width(Length::Fill)
                 .style(move |_theme| style_container(180.0, true))
-                .padding(20);
+                .padding(bar_padding);
         
                 let user_area = stack![
                         game_selector, 
@@ -309,11 +1741,72 @@ impl Launcher {
         
                 let background = state.background.as_ref().unwrap();
                 let bg_element: Element<Message> = match background {
-                    LauncherBackground::Video(video) => VideoPlayer::new(video).into(),
-                    LauncherBackground::Image(handle) => image(handle.clone()).into(),
+                    LauncherBackground::Video(video, _) => VideoPlayer::new(video)
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                        .content_fit(ContentFit::Cover)
+                        .on_streams_changed(Message::VideoStreamsChanged)
+                        .on_event(Message::BackgroundVideoEvent)
+                        .into(),
+                    LauncherBackground::Image(handle) => image(handle.clone())
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                        .content_fit(ContentFit::Cover)
+                        .into(),
                 };
 
-                stack![bg_element, user_area].into()
+                let mut layers = stack![bg_element, user_area];
+                if let Some(form) = state.server_list.as_ref() {
+                    let local_running = state.server_installs.contains_key(&state.selected_game)
+                        .then(|| state.supervisor.is_running(&state.selected_game, ProcessKind::Server));
+                    let servers = state.remote_servers.iter().filter(|server| server.game == state.selected_game).collect();
+                    layers = layers.push(get_server_list_view(
+                        &state.selected_game,
+                        local_running,
+                        servers,
+                        &state.server_statuses,
+                        state.selected_servers.get(&state.selected_game),
+                        form,
+                    ));
+                }
+                if let Some(editor) = state.config_editor.as_ref() {
+                    layers = layers.push(get_server_config_view(editor));
+                }
+                if let Some(viewer) = state.server_log.as_ref() {
+                    layers = layers.push(get_server_log_view(viewer));
+                }
+                if state.show_sessions {
+                    layers = layers.push(get_sessions_view(&state.selected_game, &state.sessions));
+                }
+                if let Some(panel) = state.runner_panel.as_ref() {
+                    let settings = state.runner_settings.get(&panel.game).cloned().unwrap_or_default();
+                    layers = layers.push(get_runner_view(panel, &settings));
+                }
+                if let Some(panel) = state.settings_panel.as_ref() {
+                    layers = layers.push(get_settings_view(panel, state.language.as_deref()));
+                }
+                if let Some(gallery) = state.gallery.as_ref() {
+                    layers = layers.push(get_gallery_view(gallery));
+                }
+                if state.show_debug_overlay {
+                    if let LauncherBackground::Video(video, _) = background {
+                        layers = layers.push(get_debug_overlay(video));
+                    }
+                }
+                if let Some(diagnostics) = state.diagnostics.as_ref() {
+                    layers = layers.push(get_diagnostics_view(diagnostics));
+                }
+                if let Some(report) = state.crash_report.as_ref() {
+                    layers = layers.push(get_crash_report_dialog(report));
+                }
+                if let Some(action) = state.pending_deep_link.as_ref() {
+                    layers = layers.push(get_deep_link_prompt(action));
+                }
+                if !state.window.maximized {
+                    layers = layers.push(get_resize_handles());
+                }
+
+                layers.into()
             }
         }
     }
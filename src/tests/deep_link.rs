@@ -0,0 +1,77 @@
+use url::Url;
+
+use crate::{utils::{deep_link::{parse_deep_link, DeepLinkAction}, game_registry::get_game_definition, server_list::{RemoteServer, ServerProtocol}}, PossibleGames};
+
+fn parse(link: &str) -> Result<DeepLinkAction, String> {
+    parse_deep_link(&Url::parse(link).unwrap())
+}
+
+#[test]
+fn parses_launch_links() {
+    assert_eq!(parse("rrlauncher://launch/zenlesszonezero"), Ok(DeepLinkAction::Launch { game: PossibleGames::ZenlessZoneZero, profile: None }));
+    assert_eq!(
+        parse("rrlauncher://launch/zenlesszonezero?profile=Test%20server"),
+        Ok(DeepLinkAction::Launch { game: PossibleGames::ZenlessZoneZero, profile: Some("Test server".to_string()) })
+    );
+    // an empty profile is the selected one
+    assert_eq!(parse("rrlauncher://launch/zenlesszonezero?profile="), Ok(DeepLinkAction::Launch { game: PossibleGames::ZenlessZoneZero, profile: None }));
+}
+
+#[test]
+fn parses_add_server_links() {
+    assert_eq!(
+        parse("rrlauncher://server/add?game=genshinimpact&host=play.example.com&port=22102&name=EU&region=eu&protocol=UDP"),
+        Ok(DeepLinkAction::AddServer(RemoteServer {
+            game: PossibleGames::GenshinImpact,
+            name: "EU".to_string(),
+            host: "play.example.com".to_string(),
+            port: 22102,
+            region: Some("eu".to_string()),
+            protocol: ServerProtocol::Udp,
+        }))
+    );
+
+    let port = get_game_definition(&PossibleGames::GenshinImpact).default_server_port;
+    assert_eq!(
+        parse("rrlauncher://server/add?game=genshinimpact&host=127.0.0.1"),
+        Ok(DeepLinkAction::AddServer(RemoteServer {
+            game: PossibleGames::GenshinImpact,
+            name: format!("127.0.0.1:{port}"),
+            host: "127.0.0.1".to_string(),
+            port,
+            region: None,
+            protocol: ServerProtocol::Tcp,
+        }))
+    );
+}
+
+#[test]
+fn rejects_invalid_links() {
+    for link in [
+        "https://launch/genshinimpact",
+        "rrlauncher://launch/",
+        "rrlauncher://launch/minecraft",
+        "rrlauncher://launch/genshinimpact/now",
+        "rrlauncher://server/add?host=127.0.0.1",
+        "rrlauncher://server/add?game=genshinimpact",
+        "rrlauncher://server/add?game=genshinimpact&host=bad%20host",
+        "rrlauncher://server/add?game=genshinimpact&host=127.0.0.1&port=0",
+        "rrlauncher://server/add?game=genshinimpact&host=127.0.0.1&port=70000",
+        "rrlauncher://server/add?game=genshinimpact&host=127.0.0.1&protocol=quic",
+        "rrlauncher://server/remove?game=genshinimpact&host=127.0.0.1",
+    ] {
+        assert!(parse(link).is_err(), "{link}");
+    }
+}
+
+#[test]
+fn launches_only_ask_when_they_start_more_than_the_game() {
+    let launch = DeepLinkAction::Launch { game: PossibleGames::WutheringWaves, profile: None };
+    assert!(!launch.modifies_state(false));
+    assert!(launch.modifies_state(true));
+
+    let Ok(add_server) = parse("rrlauncher://server/add?game=wutheringwaves&host=127.0.0.1") else {
+        panic!("add server link did not parse");
+    };
+    assert!(add_server.modifies_state(false));
+}
@@ -0,0 +1,47 @@
+//! Hooks run through `sh -c`, so these use small shell one-liners.
+
+use crate::utils::hooks::{run_hook, Hook, HookStage};
+use std::fs;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+fn hook(command: &str) -> Hook {
+    Hook { command: command.to_string(), timeout_secs: 5, abort_on_failure: true }
+}
+
+#[test]
+fn returns_while_a_background_process_holds_the_output() {
+    let dir = tempfile::tempdir().unwrap();
+    let pid_file = dir.path().join("pid");
+
+    let started = Instant::now();
+    let result = run_hook(&hook(&format!("sleep 60 & echo $! > \"{}\"", pid_file.display())), HookStage::PreLaunch, &[]);
+    let elapsed = started.elapsed();
+
+    if let Ok(pid) = fs::read_to_string(&pid_file) {
+        let _ = Command::new("kill").arg(pid.trim()).status();
+    }
+    assert_eq!(result, Ok(()));
+    assert!(elapsed < Duration::from_secs(5), "took {elapsed:?}");
+}
+
+#[test]
+fn passes_launch_env() {
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("env");
+    let env = [("RR_GAME".to_string(), "genshinimpact".to_string())];
+
+    let result = run_hook(&hook(&format!("echo \"$RR_HOOK $RR_GAME\" > \"{}\"", out.display())), HookStage::PostExit, &env);
+    assert_eq!(result, Ok(()));
+    assert_eq!(fs::read_to_string(&out).unwrap().trim(), "post-exit genshinimpact");
+}
+
+#[test]
+fn reports_failures_and_timeouts() {
+    assert!(run_hook(&hook("exit 3"), HookStage::PreLaunch, &[]).is_err());
+
+    let started = Instant::now();
+    let result = run_hook(&Hook { timeout_secs: 1, ..hook("sleep 30") }, HookStage::PreLaunch, &[]);
+    assert!(result.is_err_and(|err| err.contains("timed out")));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
@@ -0,0 +1,31 @@
+use crate::utils::language::{get_preferred_language, normalize_language};
+
+#[test]
+fn maps_iso_639_codes_to_the_same_language() {
+    for (tags, code) in [
+        (["ja", "jpn", "JPN"].as_slice(), "ja"),
+        (&["de", "ger", "deu", "de-AT", "de_DE.UTF-8"], "de"),
+        (&["zh", "chi", "zho", "zh-Hans", "zh_CN"], "zh"),
+        (&["fr", "fre", "fra"], "fr"),
+        (&["en", "eng", "en_US.UTF-8"], "en"),
+    ] {
+        for tag in tags {
+            assert_eq!(normalize_language(tag), Some(code), "{tag}");
+        }
+    }
+}
+
+#[test]
+fn rejects_unknown_tags() {
+    assert_eq!(normalize_language(""), None);
+    assert_eq!(normalize_language("und"), None);
+    assert_eq!(normalize_language("C.UTF-8"), None);
+    // a prefix of another code is not a match
+    assert_eq!(normalize_language("j"), None);
+}
+
+#[test]
+fn setting_wins_over_the_system_language() {
+    assert_eq!(get_preferred_language(Some("ko")), Some("ko"));
+    assert_eq!(get_preferred_language(Some("kor")), Some("ko"));
+}
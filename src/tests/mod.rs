@@ -0,0 +1,10 @@
+mod deep_link;
+#[cfg(unix)]
+mod hooks;
+mod language;
+#[cfg(unix)]
+mod runner;
+mod server_config;
+mod server_log;
+mod sessions;
+mod single_instance;
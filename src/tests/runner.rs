@@ -0,0 +1,126 @@
+//! Checks against a stub `wine` script that records how it was invoked,
+//! so wrapping and prefix handling can be verified without a real Wine install.
+
+use crate::utils::runner::{create_prefix, discover_proton_in, discover_wine_in, reset_prefix, wrap_command, RunnerKind, RunnerSettings};
+use std::collections::BTreeMap;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Command;
+
+/// Writes an executable script that appends its arguments, working directory and the wine variables to `log`.
+fn write_stub(path: &Path, log: &Path) {
+    let script = format!(
+        "#!/bin/sh\n{{\n  echo \"args=$*\"\n  echo \"cwd=$(pwd)\"\n  echo \"WINEPREFIX=$WINEPREFIX\"\n  echo \"STEAM_COMPAT_DATA_PATH=$STEAM_COMPAT_DATA_PATH\"\n  echo \"WINEDLLOVERRIDES=$WINEDLLOVERRIDES\"\n  echo \"PROTON_USE_WINED3D=$PROTON_USE_WINED3D\"\n  echo \"GAME_ENV=$GAME_ENV\"\n  echo \"DXVK_HUD=$DXVK_HUD\"\n}} >> \"{}\"\n",
+        log.display()
+    );
+    fs::create_dir_all(path.parent().unwrap()).expect("create stub dir");
+    fs::write(path, script).expect("write stub");
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).expect("make stub executable");
+}
+
+fn recorded(log: &Path) -> String {
+    fs::read_to_string(log).expect("stub was invoked")
+}
+
+#[test]
+fn discovers_wine_in_search_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let empty = dir.path().join("empty");
+    let bin = dir.path().join("bin");
+    fs::create_dir_all(&empty).unwrap();
+    write_stub(&bin.join("wine"), &dir.path().join("log"));
+
+    let runner = discover_wine_in([empty, bin.clone()]).expect("stub wine is found");
+    assert_eq!(runner.kind, RunnerKind::Wine);
+    assert_eq!(runner.path, bin.join("wine"));
+}
+
+#[test]
+fn discovers_proton_builds() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("log");
+    write_stub(&dir.path().join("Proton 9.0/proton"), &log);
+    write_stub(&dir.path().join("GE-Proton9-20/proton"), &log);
+    fs::create_dir_all(dir.path().join("Steamworks Shared")).unwrap();
+
+    let runners = discover_proton_in([dir.path().to_path_buf()]);
+    let names: Vec<&str> = runners.iter().map(|runner| runner.name.as_str()).collect();
+    assert_eq!(names, ["GE-Proton9-20", "Proton 9.0"]);
+    assert!(runners.iter().all(|runner| runner.kind == RunnerKind::Proton));
+}
+
+#[test]
+fn wraps_client_command_with_wine() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("log");
+    let bin = dir.path().join("bin");
+    let install = dir.path().join("install");
+    let prefix = dir.path().join("prefix");
+    fs::create_dir_all(&install).unwrap();
+    write_stub(&bin.join("wine"), &log);
+    let runner = discover_wine_in([bin]).unwrap();
+
+    let mut client = Command::new(install.join("Game.exe"));
+    client.arg("-server=127.0.0.1").current_dir(&install).env("GAME_ENV", "from-profile");
+    let settings = RunnerSettings {
+        runner: Some(runner.clone()),
+        dxvk: true,
+        dll_overrides: BTreeMap::from([("version".to_string(), "n,b".to_string())]),
+        env: BTreeMap::from([("DXVK_HUD".to_string(), "fps".to_string())]),
+    };
+
+    let status = wrap_command(&client, &runner, &settings, &prefix).status().expect("run stub");
+    assert!(status.success());
+
+    let recorded = recorded(&log);
+    assert!(recorded.contains(&format!("args={} -server=127.0.0.1", install.join("Game.exe").display())), "{recorded}");
+    assert!(recorded.contains(&format!("cwd={}", install.display())), "{recorded}");
+    assert!(recorded.contains(&format!("WINEPREFIX={}", prefix.display())), "{recorded}");
+    // dxvk is proton only, a plain wine prefix has no dxvk dlls to prefer
+    assert!(recorded.contains("WINEDLLOVERRIDES=version=n,b\n"), "{recorded}");
+    assert!(recorded.contains("GAME_ENV=from-profile"), "{recorded}");
+    assert!(recorded.contains("DXVK_HUD=fps"), "{recorded}");
+}
+
+#[test]
+fn wraps_client_command_with_proton() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("log");
+    let prefix = dir.path().join("prefix");
+    write_stub(&dir.path().join("Proton 9.0/proton"), &log);
+    let runner = discover_proton_in([dir.path().to_path_buf()]).pop().unwrap();
+
+    let client = Command::new("Game.exe");
+    let settings = RunnerSettings { runner: Some(runner.clone()), dxvk: false, ..RunnerSettings::default() };
+    let status = wrap_command(&client, &runner, &settings, &prefix).status().expect("run stub");
+    assert!(status.success());
+
+    let recorded = recorded(&log);
+    assert!(recorded.contains("args=run Game.exe"), "{recorded}");
+    assert!(recorded.contains(&format!("STEAM_COMPAT_DATA_PATH={}", prefix.display())), "{recorded}");
+    assert!(recorded.contains("WINEDLLOVERRIDES=\n"), "{recorded}");
+    assert!(recorded.contains("PROTON_USE_WINED3D=1"), "{recorded}");
+}
+
+#[test]
+fn creates_and_resets_prefix() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("log");
+    let bin = dir.path().join("bin");
+    let prefix = dir.path().join("prefix");
+    write_stub(&bin.join("wine"), &log);
+    let runner = discover_wine_in([bin]).unwrap();
+
+    create_prefix(&runner, &prefix).expect("create prefix");
+    assert!(prefix.is_dir());
+    fs::write(prefix.join("leftover"), "").unwrap();
+
+    reset_prefix(&runner, &prefix).expect("reset prefix");
+    assert!(prefix.is_dir());
+    assert!(!prefix.join("leftover").exists());
+
+    let recorded = recorded(&log);
+    assert_eq!(recorded.matches("args=wineboot --init").count(), 2, "{recorded}");
+    assert!(recorded.contains(&format!("WINEPREFIX={}", prefix.display())), "{recorded}");
+}
@@ -0,0 +1,104 @@
+use std::{fs, sync::Arc};
+
+use iced::widget::text_editor::{Action, Edit};
+use serde_json::{json, Value};
+
+use crate::{utils::{game_registry::{ConfigField, ConfigFieldKind}, server_config::{get_value, parse_field, set_value, ConfigEditor}}, PossibleGames};
+
+const PORT: ConfigField = ConfigField { key: "network.port", label: "Port", kind: ConfigFieldKind::Port, default: "5500" };
+const PLAYERS: ConfigField = ConfigField { key: "gameplay.max_players", label: "Max players", kind: ConfigFieldKind::Integer { min: 1, max: 1000 }, default: "50" };
+const DEBUG: ConfigField = ConfigField { key: "debug", label: "Debug", kind: ConfigFieldKind::Bool, default: "false" };
+const HOST: ConfigField = ConfigField { key: "network.host", label: "Listen address", kind: ConfigFieldKind::Text, default: "0.0.0.0" };
+
+#[test]
+fn parses_valid_values() {
+    assert_eq!(parse_field(&PORT, "7000"), Ok(json!(7000)));
+    assert_eq!(parse_field(&PLAYERS, " 1000 "), Ok(json!(1000)));
+    assert_eq!(parse_field(&DEBUG, "true"), Ok(json!(true)));
+    assert_eq!(parse_field(&HOST, " 127.0.0.1 "), Ok(json!("127.0.0.1")));
+    // empty text is the default
+    assert_eq!(parse_field(&PORT, ""), Ok(json!(5500)));
+    assert_eq!(parse_field(&PLAYERS, "  "), Ok(json!(50)));
+}
+
+#[test]
+fn rejects_invalid_values() {
+    assert!(parse_field(&PORT, "http").is_err());
+    assert!(parse_field(&PLAYERS, "12.5").is_err());
+    assert!(parse_field(&DEBUG, "yes").is_err());
+}
+
+#[test]
+fn rejects_out_of_range_values() {
+    assert!(parse_field(&PORT, "0").is_err());
+    assert!(parse_field(&PORT, "65536").is_err());
+    assert!(parse_field(&PLAYERS, "0").is_err());
+    assert!(parse_field(&PLAYERS, "1001").is_err());
+    assert!(parse_field(&PLAYERS, "-5").is_err());
+}
+
+#[test]
+fn sets_nested_values() {
+    let mut document = json!({"network": {"host": "0.0.0.0"}, "debug": 1});
+    set_value(&mut document, "network.port", json!(7000));
+    // a value in the way of a table is replaced by one
+    set_value(&mut document, "debug.enabled", json!(true));
+    assert_eq!(document, json!({"network": {"host": "0.0.0.0", "port": 7000}, "debug": {"enabled": true}}));
+    assert_eq!(get_value(&document, "network.port"), Some(&json!(7000)));
+    assert_eq!(get_value(&document, "network.missing"), None);
+}
+
+fn open_editor(contents: &str) -> (tempfile::TempDir, ConfigEditor) {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("config.toml"), contents).unwrap();
+    let editor = ConfigEditor::open(&PossibleGames::WutheringWaves, dir.path()).unwrap();
+    (dir, editor)
+}
+
+fn read_config(dir: &tempfile::TempDir) -> Value {
+    let contents = fs::read_to_string(dir.path().join("config.toml")).unwrap();
+    toml::from_str(&contents).unwrap()
+}
+
+fn replace_raw(editor: &mut ConfigEditor, text: &str) {
+    editor.edit_raw(Action::SelectAll);
+    editor.edit_raw(Action::Edit(Edit::Paste(Arc::new(text.to_string()))));
+}
+
+#[test]
+fn saves_the_form_over_unknown_settings() {
+    let (dir, mut editor) = open_editor("[network]\nport = 6000\n\n[extra]\nkept = 1\n");
+    editor.set_field(1, "7000".to_string());
+    let backup = editor.save().unwrap().expect("the previous config is backed up");
+
+    assert_eq!(fs::read_to_string(backup).unwrap(), "[network]\nport = 6000\n\n[extra]\nkept = 1\n");
+    let config = read_config(&dir);
+    assert_eq!(get_value(&config, "network.port"), Some(&json!(7000)));
+    assert_eq!(get_value(&config, "gameplay.max_players"), Some(&json!(50)));
+    assert_eq!(get_value(&config, "extra.kept"), Some(&json!(1)));
+}
+
+#[test]
+fn refuses_to_save_invalid_form_values() {
+    let (dir, mut editor) = open_editor("[network]\nport = 6000\n");
+    editor.set_field(2, "5000".to_string());
+    assert!(editor.save().is_err());
+    assert_eq!(fs::read_to_string(dir.path().join("config.toml")).unwrap(), "[network]\nport = 6000\n");
+}
+
+#[test]
+fn validates_raw_text_before_writing_it_as_typed() {
+    let (dir, mut editor) = open_editor("[network]\nport = 6000\n");
+    editor.toggle_raw().unwrap();
+
+    replace_raw(&mut editor, "[gameplay]\nmax_players = 5000\n");
+    assert!(editor.save().is_err());
+    replace_raw(&mut editor, "[network\nport = 6000\n");
+    assert!(editor.save().is_err());
+    assert_eq!(fs::read_to_string(dir.path().join("config.toml")).unwrap(), "[network]\nport = 6000\n");
+
+    let text = "# keep this comment\n[network]\nport   = 7000\n";
+    replace_raw(&mut editor, text);
+    editor.save().unwrap();
+    assert_eq!(fs::read_to_string(dir.path().join("config.toml")).unwrap(), text);
+}
@@ -0,0 +1,75 @@
+use iced::Color;
+
+use crate::utils::server_log::{apply_sgr, detect_level, parse_ansi, parse_log_line, LogLevel, LogSpan, LogStream};
+
+const RED: Color = Color::from_rgb8(222, 56, 43);
+const BRIGHT_GREEN: Color = Color::from_rgb8(85, 255, 85);
+
+fn span(text: &str, color: Option<Color>) -> LogSpan {
+    LogSpan { text: text.to_string(), color }
+}
+
+#[test]
+fn applies_sgr_colors() {
+    let mut color = None;
+    apply_sgr("31", &mut color);
+    assert_eq!(color, Some(RED));
+    // attributes like bold are ignored, the colour after them still applies
+    apply_sgr("1;92", &mut color);
+    assert_eq!(color, Some(BRIGHT_GREEN));
+    apply_sgr("39", &mut color);
+    assert_eq!(color, None);
+
+    apply_sgr("38;5;1", &mut color);
+    assert_eq!(color, Some(RED));
+    apply_sgr("38;5;10", &mut color);
+    assert_eq!(color, Some(BRIGHT_GREEN));
+    // beyond the 16 basic entries of the palette
+    apply_sgr("38;5;200", &mut color);
+    assert_eq!(color, None);
+
+    apply_sgr("38;2;1;2;300", &mut color);
+    assert_eq!(color, Some(Color::from_rgb8(1, 2, 255)));
+    // an empty sequence is a reset
+    apply_sgr("", &mut color);
+    assert_eq!(color, None);
+}
+
+#[test]
+fn splits_lines_into_colored_spans() {
+    assert_eq!(parse_ansi("plain"), vec![span("plain", None)]);
+    assert_eq!(
+        parse_ansi("\u{1b}[31mERROR\u{1b}[0m: failed"),
+        vec![span("ERROR", Some(RED)), span(": failed", None)]
+    );
+    // a colour set twice in a row doesn't split the text
+    assert_eq!(parse_ansi("\u{1b}[31ma\u{1b}[31mb"), vec![span("ab", Some(RED))]);
+}
+
+#[test]
+fn drops_other_escape_sequences() {
+    assert_eq!(parse_ansi("\u{1b}[2Kloading\u{1b}[1G"), vec![span("loading", None)]);
+    assert_eq!(parse_ansi("\u{1b}[31m\u{1b}[0m"), Vec::new());
+}
+
+#[test]
+fn detects_levels() {
+    assert_eq!(detect_level("2024-05-01 12:00:00 INFO Server started"), Some(LogLevel::Info));
+    assert_eq!(detect_level("[WARN] slow tick"), Some(LogLevel::Warn));
+    assert_eq!(detect_level("warning: deprecated option"), Some(LogLevel::Warn));
+    assert_eq!(detect_level("FATAL out of memory"), Some(LogLevel::Error));
+    assert_eq!(detect_level("err: bind failed"), Some(LogLevel::Error));
+    assert_eq!(detect_level("trace: packet 12"), Some(LogLevel::Trace));
+    // the first level wins over ones in the message
+    assert_eq!(detect_level("DEBUG retrying after error"), Some(LogLevel::Debug));
+    // only whole words count
+    assert_eq!(detect_level("terrorist information"), None);
+    assert_eq!(detect_level("player joined"), None);
+}
+
+#[test]
+fn detects_levels_after_removing_colors() {
+    let line = parse_log_line(LogStream::Stderr, "\u{1b}[33mWARN\u{1b}[0m disk almost full");
+    assert_eq!(line.text, "WARN disk almost full");
+    assert_eq!(line.level, Some(LogLevel::Warn));
+}
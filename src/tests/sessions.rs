@@ -0,0 +1,49 @@
+use chrono::{TimeZone, Utc};
+
+use crate::{utils::{sessions::{escape_csv, get_sessions_csv, Session}, supervisor::ProcessKind}, PossibleGames};
+
+#[test]
+fn escapes_csv_fields() {
+    assert_eq!(escape_csv("main"), "main");
+    assert_eq!(escape_csv(""), "");
+    assert_eq!(escape_csv("eu, test"), "\"eu, test\"");
+    assert_eq!(escape_csv("the \"fast\" one"), "\"the \"\"fast\"\" one\"");
+    assert_eq!(escape_csv("two\nlines"), "\"two\nlines\"");
+    assert_eq!(escape_csv("two\r\nlines"), "\"two\r\nlines\"");
+    assert_eq!(escape_csv("carriage\rreturn"), "\"carriage\rreturn\"");
+}
+
+#[test]
+fn exports_one_row_per_session() {
+    let started = Utc.with_ymd_and_hms(2025, 3, 1, 18, 0, 0).unwrap();
+    let sessions = [
+        Session {
+            game: PossibleGames::HonkaiStarRail,
+            kind: ProcessKind::Client,
+            started,
+            ended: Utc.with_ymd_and_hms(2025, 3, 1, 19, 30, 15).unwrap(),
+            exit_code: Some(0),
+            profile: Some("main, \"alt\"".to_string()),
+        },
+        Session {
+            game: PossibleGames::HonkaiStarRail,
+            kind: ProcessKind::Server,
+            started,
+            ended: Utc.with_ymd_and_hms(2025, 3, 1, 18, 0, 5).unwrap(),
+            exit_code: None,
+            profile: None,
+        },
+    ];
+
+    assert_eq!(
+        get_sessions_csv(&sessions),
+        "game,kind,started,ended,duration_seconds,exit_code,profile\n\
+        honkaistarrail,client,2025-03-01T18:00:00+00:00,2025-03-01T19:30:15+00:00,5415,0,\"main, \"\"alt\"\"\"\n\
+        honkaistarrail,server,2025-03-01T18:00:00+00:00,2025-03-01T18:00:05+00:00,5,,\n"
+    );
+}
+
+#[test]
+fn exports_only_the_header_without_sessions() {
+    assert_eq!(get_sessions_csv(&[]), "game,kind,started,ended,duration_seconds,exit_code,profile\n");
+}
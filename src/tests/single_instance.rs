@@ -0,0 +1,33 @@
+use crate::{utils::single_instance::{parse_launcher_command, LauncherCommand}, PossibleGames};
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn no_arguments_is_no_command() {
+    assert_eq!(parse_launcher_command(&[]), Ok(None));
+}
+
+#[test]
+fn parses_launch_by_slug() {
+    assert_eq!(parse_launcher_command(&args(&["launch", "genshinimpact"])), Ok(Some(LauncherCommand::Launch(PossibleGames::GenshinImpact))));
+    // slugs are matched case-insensitively
+    assert_eq!(parse_launcher_command(&args(&["launch", "HonkaiStarRail"])), Ok(Some(LauncherCommand::Launch(PossibleGames::HonkaiStarRail))));
+    assert!(parse_launcher_command(&args(&["launch", "minecraft"])).is_err());
+    assert!(parse_launcher_command(&args(&["launch"])).is_err());
+}
+
+#[test]
+fn parses_deep_links() {
+    let link = "rrlauncher://launch/wutheringwaves?profile=main";
+    assert_eq!(parse_launcher_command(&args(&[link])), Ok(Some(LauncherCommand::DeepLink(url::Url::parse(link).unwrap()))));
+    // only the launcher's own scheme is a link
+    assert!(parse_launcher_command(&args(&["https://example.com"])).is_err());
+}
+
+#[test]
+fn rejects_unknown_commands() {
+    assert!(parse_launcher_command(&args(&["update"])).is_err());
+    assert!(parse_launcher_command(&args(&["launch", "genshinimpact", "now"])).is_err());
+}
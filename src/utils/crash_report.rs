@@ -0,0 +1,188 @@
+use std::{
+    fs::{self, File}, io::{self, Read, Seek, SeekFrom, Write}, path::{Path, PathBuf}, process::ExitStatus, time::SystemTime
+};
+
+use chrono::{DateTime, Local};
+use iced::{
+    futures::channel::oneshot, widget::{column, row, text},
+    Alignment::Center, Element
+};
+use sysinfo::System;
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+use crate::{Message, PossibleGames};
+
+use super::{game_registry::get_game_definition, paths::get_data_dir, supervisor::ProcessKind, widgets::{get_dialog_button, get_modal}};
+
+/// Output lines kept in a report.
+pub const CRASH_OUTPUT_LINES: usize = 1000;
+// only the end of a log file is relevant to a crash, and some games never truncate theirs
+const MAX_LOG_FILE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// What is known about a process that ended abnormally, gathered on the ui thread before the bundle is written.
+#[derive(Debug, Clone)]
+pub struct CrashInfo {
+    pub game: PossibleGames,
+    pub kind: ProcessKind,
+    pub started: SystemTime,
+    pub ended: SystemTime,
+    pub status: Option<ExitStatus>,
+    pub profile: Option<String>,
+    /// Last lines of captured output, oldest first.
+    pub output: Vec<String>,
+    /// Install directory the registry's log files are relative to.
+    pub install_dir: Option<PathBuf>,
+}
+
+impl CrashInfo {
+    fn process_name(&self) -> String {
+        let name = get_game_definition(&self.game).display_name;
+        match self.kind {
+            ProcessKind::Client => name.to_string(),
+            ProcessKind::Server => format!("The {name} server"),
+        }
+    }
+}
+
+/// A crash report that was written, shown in the crash dialog.
+#[derive(Debug, Clone)]
+pub struct CrashReport {
+    pub title: String,
+    pub path: PathBuf,
+}
+
+pub fn describe_exit(status: Option<ExitStatus>) -> String {
+    let Some(status) = status else {
+        return "an unknown status".to_string();
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return format!("signal {signal}{}", if status.core_dumped() {" (core dumped)"} else {""});
+        }
+    }
+    match status.code() {
+        Some(code) => format!("exit code {code}"),
+        None => status.to_string(),
+    }
+}
+
+/// Crash reports are written to `<data dir>/crash-reports/`.
+pub fn get_crash_report_dir() -> Option<PathBuf> {
+    Some(get_data_dir()?.join("crash-reports"))
+}
+
+fn get_summary(info: &CrashInfo) -> String {
+    let format_time = |time: SystemTime| DateTime::<Local>::from(time).format("%Y-%m-%d %H:%M:%S %z").to_string();
+    let mut summary = format!(
+        "Game: {}\nProcess: {}\nExit: {}\nStarted: {}\nEnded: {}\nProfile: {}\n",
+        get_game_definition(&info.game).display_name,
+        match info.kind {ProcessKind::Client => "client", ProcessKind::Server => "server"},
+        describe_exit(info.status),
+        format_time(info.started),
+        format_time(info.ended),
+        info.profile.as_deref().unwrap_or("default"),
+    );
+    if let Some(dir) = &info.install_dir {
+        summary.push_str(&format!("Install: {}\n", dir.display()));
+    }
+    summary
+}
+
+fn get_system_info() -> String {
+    let mut system = System::new();
+    system.refresh_memory();
+    system.refresh_cpu_all();
+
+    let cpu = system.cpus().first().map(|cpu| cpu.brand().trim().to_string()).unwrap_or_default();
+    format!(
+        "Launcher: {}\nOS: {} {}\nKernel: {}\nArchitecture: {}\nCPU: {cpu} ({} threads)\nMemory: {} MiB used of {} MiB\n",
+        env!("CARGO_PKG_VERSION"),
+        System::name().unwrap_or_default(),
+        System::os_version().unwrap_or_default(),
+        System::kernel_version().unwrap_or_default(),
+        std::env::consts::ARCH,
+        system.cpus().len(),
+        system.used_memory() / 1024 / 1024,
+        system.total_memory() / 1024 / 1024,
+    )
+}
+
+/// Reads at most the last [`MAX_LOG_FILE_BYTES`] of a file.
+fn read_log_tail(path: &Path) -> io::Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(MAX_LOG_FILE_BYTES)))?;
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents)?;
+    Ok(contents)
+}
+
+/// Writes `<game>-<client|server>-<time>.zip` with a summary, the captured output, the registry's log files and system info.
+pub fn write_crash_report(info: &CrashInfo) -> io::Result<PathBuf> {
+    let dir = get_crash_report_dir().ok_or(io::ErrorKind::NotFound)?;
+    fs::create_dir_all(&dir)?;
+
+    let definition = get_game_definition(&info.game);
+    let kind = match info.kind {ProcessKind::Client => "client", ProcessKind::Server => "server"};
+    let timestamp = DateTime::<Local>::from(info.ended).format("%Y%m%d-%H%M%S");
+    let path = dir.join(format!("{}-{kind}-{timestamp}.zip", definition.slug));
+
+    let mut zip = ZipWriter::new(File::create(&path)?);
+    let options = SimpleFileOptions::default();
+
+    zip.start_file("summary.txt", options)?;
+    zip.write_all(get_summary(info).as_bytes())?;
+
+    zip.start_file("system.txt", options)?;
+    zip.write_all(get_system_info().as_bytes())?;
+
+    if !info.output.is_empty() {
+        zip.start_file("output.log", options)?;
+        for line in &info.output {
+            writeln!(zip, "{line}")?;
+        }
+    }
+
+    let log_files = match info.kind {
+        ProcessKind::Client => definition.client_log_files,
+        ProcessKind::Server => definition.server_log_files,
+    };
+    if let Some(install_dir) = &info.install_dir {
+        for log_file in log_files {
+            // missing logs are normal, e.g. the other region's data folder
+            let Ok(contents) = read_log_tail(&install_dir.join(log_file)) else {
+                continue;
+            };
+            zip.start_file(format!("logs/{log_file}"), options)?;
+            zip.write_all(&contents)?;
+        }
+    }
+
+    zip.finish()?;
+    Ok(path)
+}
+
+/// Runs [`write_crash_report`] on a thread, reading large logs shouldn't stall the ui.
+pub async fn create_crash_report(info: CrashInfo) -> Result<CrashReport, String> {
+    let (sender, receiver) = oneshot::channel();
+    std::thread::spawn(move || {
+        let result = write_crash_report(&info)
+            .map(|path| CrashReport { title: format!("{} stopped with {}", info.process_name(), describe_exit(info.status)), path })
+            .map_err(|err| format!("Failed to write a crash report: {err}"));
+        let _ = sender.send(result);
+    });
+    receiver.await.unwrap_or_else(|_| Err("Writing the crash report stopped unexpectedly".to_string()))
+}
+
+pub fn get_crash_report_dialog(report: &CrashReport) -> Element<Message> {
+    get_modal(column![
+        text(&report.title).size(22),
+        text(format!("A crash report was saved to {}", report.path.display())).size(15),
+        row![
+            get_dialog_button("Close", Message::DismissCrashReport, false),
+            get_dialog_button("Open folder", Message::OpenCrashReportFolder, true),
+        ].spacing(10),
+    ].spacing(15).align_x(Center), 520.0)
+}
@@ -0,0 +1,34 @@
+use iced::{widget::{column, container, text}, Color, Element, Length};
+use iced_video_player::Video;
+
+use crate::Message;
+
+pub fn get_debug_overlay(video: &Video) -> Element<Message> {
+    let stats = video.stats();
+    let buffer_fill = stats.buffer_fill.map_or("n/a".to_string(), |percent| format!("{percent}%"));
+    let decoders = if stats.decoders.is_empty() {"none".to_string()} else {stats.decoders.join(", ")};
+
+    let lines = column![
+        text(format!("frames: {} decoded, {} presented, {} dropped", stats.frames_decoded, stats.frames_presented, stats.frames_dropped)).size(14),
+        text(format!("a/v offset: {:.2} ms", stats.av_offset.as_secs_f64() * 1000.0)).size(14),
+        text(format!("buffer: {buffer_fill}")).size(14),
+        text(format!("decoders: {decoders}")).size(14),
+        text(format!("caps: {}", stats.caps.unwrap_or_else(|| "none".to_string()))).size(14),
+    ]
+    .spacing(2);
+
+    container(
+        container(lines)
+            .padding(10)
+            .max_width(500)
+            .style(move |_| container::Style {
+                text_color: Color::from_rgba8(255, 255, 255, 1.0).into(),
+                background: Some(Color::from_rgba8(0, 0, 0, 0.6).into()),
+                ..container::Style::default()
+            })
+    )
+    .padding([90, 20])
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
@@ -0,0 +1,155 @@
+use std::{collections::HashMap, io};
+
+use iced::{
+    widget::{column, row, text}, Alignment::Center, Element
+};
+use url::Url;
+
+use crate::{Message, PossibleGames};
+
+use super::{game_registry::{get_game_by_slug, get_game_definition}, server_list::{RemoteServer, ServerProtocol}, widgets::{get_dialog_button, get_modal}};
+
+pub const URL_SCHEME: &str = "rrlauncher";
+
+/// What a `rrlauncher://` link asks the launcher to do.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeepLinkAction {
+    /// `rrlauncher://launch/<game>?profile=<name>`
+    Launch { game: PossibleGames, profile: Option<String> },
+    /// `rrlauncher://server/add?game=<game>&host=<host>&port=<port>&name=<name>&region=<region>&protocol=<tcp|udp>`,
+    /// the port defaults to the game's server port, the name to `host:port` and the protocol to tcp.
+    AddServer(RemoteServer),
+}
+
+impl DeepLinkAction {
+    /// Actions that change saved state or start more than the game are only carried out after the user confirms them.
+    /// A link can't tell whether its launch starts the local server or runs hooks, `launch_side_effects` does.
+    pub fn modifies_state(&self, launch_side_effects: bool) -> bool {
+        match self {
+            DeepLinkAction::Launch { .. } => launch_side_effects,
+            DeepLinkAction::AddServer(_) => true,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            DeepLinkAction::Launch { game, profile: None } => format!("Launch {}", get_game_definition(game).display_name),
+            DeepLinkAction::Launch { game, profile: Some(profile) } => format!("Launch {} with the \"{profile}\" profile", get_game_definition(game).display_name),
+            DeepLinkAction::AddServer(server) => format!(
+                "Add the {} server \"{}\" at {}:{}",
+                get_game_definition(&server.game).display_name,
+                server.name,
+                server.host,
+                server.port
+            ),
+        }
+    }
+}
+
+fn get_game(slug: Option<&str>) -> Result<PossibleGames, String> {
+    let slug = slug.filter(|slug| !slug.is_empty()).ok_or("The link does not name a game")?;
+    get_game_by_slug(slug).ok_or_else(|| format!("Unknown game \"{slug}\""))
+}
+
+pub fn parse_deep_link(url: &Url) -> Result<DeepLinkAction, String> {
+    if url.scheme() != URL_SCHEME {
+        return Err(format!("Not a {URL_SCHEME}:// link"));
+    }
+
+    let query: HashMap<String, String> = url.query_pairs().into_owned().collect();
+    let segments: Vec<&str> = url.path_segments().map(|segments| segments.filter(|segment| !segment.is_empty()).collect()).unwrap_or_default();
+
+    match (url.host_str(), segments.as_slice()) {
+        (Some("launch"), [slug]) => Ok(DeepLinkAction::Launch {
+            game: get_game(Some(slug))?,
+            profile: query.get("profile").filter(|profile| !profile.is_empty()).cloned(),
+        }),
+        (Some("server"), ["add"]) => {
+            let game = get_game(query.get("game").map(String::as_str))?;
+            let host = query.get("host").filter(|host| !host.is_empty()).ok_or("The link does not name a host")?;
+            url::Host::parse(host).map_err(|_| format!("Invalid host \"{host}\""))?;
+            let port = match query.get("port") {
+                Some(port) => port.parse().ok().filter(|port| *port != 0).ok_or_else(|| format!("Invalid port \"{port}\""))?,
+                None => get_game_definition(&game).default_server_port,
+            };
+            let protocol = match query.get("protocol").map(|protocol| protocol.to_lowercase()).as_deref() {
+                None | Some("tcp") => ServerProtocol::Tcp,
+                Some("udp") => ServerProtocol::Udp,
+                Some(protocol) => return Err(format!("Unknown protocol \"{protocol}\"")),
+            };
+            Ok(DeepLinkAction::AddServer(RemoteServer {
+                game,
+                name: query.get("name").filter(|name| !name.is_empty()).cloned().unwrap_or_else(|| format!("{host}:{port}")),
+                host: host.clone(),
+                port,
+                region: query.get("region").filter(|region| !region.is_empty()).cloned(),
+                protocol,
+            }))
+        }
+        _ => Err(format!("Unsupported link {url}")),
+    }
+}
+
+/// Makes the system open `rrlauncher://` links with this executable.
+#[cfg(target_os = "linux")]
+pub fn register_url_scheme() -> io::Result<()> {
+    use std::{env, fs, process::Command};
+
+    let exe = env::current_exe()?;
+    let dirs = directories::BaseDirs::new().ok_or(io::ErrorKind::NotFound)?;
+    let applications = dirs.data_dir().join("applications");
+    fs::create_dir_all(&applications)?;
+
+    let desktop_file = "reversed-rooms-launcher.desktop";
+    fs::write(applications.join(desktop_file), format!(
+        "[Desktop Entry]\nType=Application\nName=Reversed Rooms Launcher\nExec=\"{}\" %u\nTerminal=false\nNoDisplay=true\nMimeType=x-scheme-handler/{URL_SCHEME};\n",
+        exe.display()
+    ))?;
+
+    let status = Command::new("xdg-mime").args(["default", desktop_file, &format!("x-scheme-handler/{URL_SCHEME}")]).status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("xdg-mime exited with {status}")));
+    }
+    Ok(())
+}
+
+/// Makes the system open `rrlauncher://` links with this executable.
+#[cfg(target_os = "windows")]
+pub fn register_url_scheme() -> io::Result<()> {
+    use std::{env, process::Command};
+
+    let exe = env::current_exe()?;
+    let key = format!(r"HKCU\Software\Classes\{URL_SCHEME}");
+    let command = format!("\"{}\" \"%1\"", exe.display());
+    let entries = [
+        vec![key.clone(), "/ve".into(), "/d".into(), "URL:Reversed Rooms Launcher".into()],
+        vec![key.clone(), "/v".into(), "URL Protocol".into(), "/d".into(), String::new()],
+        vec![format!(r"{key}\shell\open\command"), "/ve".into(), "/d".into(), command],
+    ];
+
+    for entry in entries {
+        let status = Command::new("reg").arg("add").args(entry).arg("/f").status()?;
+        if !status.success() {
+            return Err(io::Error::other(format!("reg exited with {status}")));
+        }
+    }
+    Ok(())
+}
+
+/// Other platforms register url schemes through the app bundle, not at runtime.
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn register_url_scheme() -> io::Result<()> {
+    Ok(())
+}
+
+/// Asks before carrying out a link that changes saved state.
+pub fn get_deep_link_prompt(action: &DeepLinkAction) -> Element<Message> {
+    get_modal(column![
+        text("Open link?").size(22),
+        text(action.describe()).size(15),
+        row![
+            get_dialog_button("Cancel", Message::CancelDeepLink, false),
+            get_dialog_button("Confirm", Message::ConfirmDeepLink, true),
+        ].spacing(10),
+    ].spacing(15).align_x(Center), 480.0)
+}
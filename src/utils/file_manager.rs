@@ -0,0 +1,18 @@
+use std::{fs, io, path::Path, process::Command};
+
+/// Shows a folder in the system's file manager, creating it first so there is something to open.
+pub fn open_in_file_manager(path: &Path) -> io::Result<()> {
+    fs::create_dir_all(path)?;
+
+    let program = if cfg!(target_os = "windows") {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    // the opener returns right away, reap it on a thread instead of leaving a zombie behind
+    let mut child = Command::new(program).arg(path).spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
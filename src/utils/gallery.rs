@@ -0,0 +1,298 @@
+use std::{collections::HashMap, fs, num::NonZeroU8, path::{Path, PathBuf}, sync::Arc, time::Duration};
+
+use ::image::ImageReader;
+use iced::{
+    futures::channel::oneshot, widget::{button, column, container, image, mouse_area, row, slider, stack, text, Column, Space},
+    Alignment::Center, Color, ContentFit, Element, Font, Length, Task
+};
+use iced_video_player::{Position, SeekBar, Thumbnails, TrackKind, Video, VideoOptions, VideoPlayer};
+
+use crate::{Message, PossibleGames};
+
+use super::{game_registry::get_game_definition, icons::{get_icon, Icon}, paths::get_data_dir};
+
+const THUMBNAIL_WIDTH: f32 = 256.0;
+const THUMBNAIL_HEIGHT: f32 = 144.0;
+const GRID_COLUMNS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GalleryKind {
+    Image,
+    Video,
+}
+
+#[derive(Debug, Clone)]
+pub struct GalleryItem {
+    pub path: PathBuf,
+    pub kind: GalleryKind,
+}
+
+#[derive(Debug)]
+pub enum GalleryViewer {
+    Image(image::Handle),
+    // a video prerolling on a thread, until `Message::GalleryVideoLoaded` for this path
+    Loading(PathBuf),
+    Video {
+        video: Video,
+        // index into the subtitle tracks, `None` hides subtitles
+        subtitle_track: Option<usize>,
+        subtitle_text: Option<String>,
+    },
+}
+
+#[derive(Debug)]
+pub struct Gallery {
+    pub game: PossibleGames,
+    pub items: Vec<GalleryItem>,
+    pub thumbnails: HashMap<PathBuf, image::Handle>,
+    pub viewer: Option<GalleryViewer>,
+}
+
+/// Media for a game lives in `<data dir>/gallery/<game>/`, e.g. `~/.local/share/reversed-rooms-launcher/gallery/genshinimpact/`.
+pub fn get_gallery_dir(game: &PossibleGames) -> Option<PathBuf> {
+    Some(get_data_dir()?.join("gallery").join(get_game_definition(game).slug))
+}
+
+fn get_gallery_kind(path: &Path) -> Option<GalleryKind> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    match extension.as_str() {
+        "png" | "jpg" | "jpeg" | "webp" | "bmp" => Some(GalleryKind::Image),
+        "mp4" | "webm" | "mkv" | "mov" | "avi" => Some(GalleryKind::Video),
+        _ => None,
+    }
+}
+
+/// Only local media for now, the launcher has no announcements feed to take remote media from yet.
+pub fn get_gallery_items(game: &PossibleGames) -> Vec<GalleryItem> {
+    let Some(dir) = get_gallery_dir(game) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut items: Vec<GalleryItem> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter_map(|path| get_gallery_kind(&path).map(|kind| GalleryItem { path, kind }))
+        .collect();
+    items.sort_by(|a, b| a.path.cmp(&b.path));
+    items
+}
+
+fn load_image(path: &Path) -> Option<::image::DynamicImage> {
+    ImageReader::open(path).ok()?.with_guessed_format().ok()?.decode().ok()
+}
+
+pub fn load_gallery_image(path: &Path) -> Option<image::Handle> {
+    let img = load_image(path)?;
+    Some(image::Handle::from_rgba(img.width(), img.height(), img.to_rgba8().into_raw()))
+}
+
+/// Decodes and scales an image on a thread, large screenshots would stall the executor.
+async fn load_image_thumbnail(path: PathBuf) -> Option<image::Handle> {
+    let (sender, receiver) = oneshot::channel();
+    std::thread::spawn(move || {
+        let thumbnail = load_image(&path).map(|img| {
+            let img = img.thumbnail(THUMBNAIL_WIDTH as u32 * 2, THUMBNAIL_HEIGHT as u32 * 2);
+            image::Handle::from_rgba(img.width(), img.height(), img.to_rgba8().into_raw())
+        });
+        let _ = sender.send(thumbnail);
+    });
+    receiver.await.ok().flatten()
+}
+
+/// Loads a thumbnail for every item, videos are sampled a second in through `iced_video_player`.
+pub fn load_gallery_thumbnails(items: &[GalleryItem]) -> Task<Message> {
+    Task::batch(items.iter().cloned().map(|item| {
+        let path = item.path.clone();
+        match item.kind {
+            GalleryKind::Image => Task::perform(
+                load_image_thumbnail(item.path),
+                move |thumbnail| Message::GalleryThumbnailReady(path.clone(), thumbnail),
+            ),
+            GalleryKind::Video => {
+                let Ok(uri) = url::Url::from_file_path(&item.path) else {
+                    return Task::none();
+                };
+                let thumbnails = Thumbnails::new(
+                    &uri,
+                    [Position::Time(Duration::from_secs(1))],
+                    NonZeroU8::new(4).unwrap(),
+                    Duration::from_secs(5),
+                );
+                Task::perform(thumbnails.collect_all(), move |thumbnails| {
+                    Message::GalleryThumbnailReady(path.clone(), thumbnails.ok().and_then(|mut thumbnails| thumbnails.pop()))
+                })
+            }
+        }
+    }))
+}
+
+/// Images open right away, videos show a loading screen while [`Video::load`] prerolls them on a thread.
+pub fn open_gallery_item(item: &GalleryItem) -> (Option<GalleryViewer>, Task<Message>) {
+    match item.kind {
+        GalleryKind::Image => (load_gallery_image(&item.path).map(GalleryViewer::Image), Task::none()),
+        GalleryKind::Video => {
+            let Ok(uri) = url::Url::from_file_path(&item.path) else {
+                return (None, Task::none());
+            };
+            let path = item.path.clone();
+            let task = Task::perform(Video::load(uri, VideoOptions::default()), move |video| {
+                Message::GalleryVideoLoaded(path.clone(), video.map(Arc::new).map_err(|err| err.to_string()))
+            });
+            (Some(GalleryViewer::Loading(item.path.clone())), task)
+        }
+    }
+}
+
+/// Switches to the next subtitle track, wrapping around to no subtitles after the last one.
+pub fn cycle_subtitles(video: &mut Video, subtitle_track: &mut Option<usize>) {
+    let tracks = video.tracks(TrackKind::Subtitle);
+    let next = match subtitle_track {
+        None => tracks.first(),
+        Some(current) => tracks.iter().skip_while(|track| track.index != *current).nth(1),
+    };
+    *subtitle_track = next.map(|track| track.index);
+    if let Some(index) = *subtitle_track {
+        let _ = video.set_track(TrackKind::Subtitle, index);
+    }
+}
+
+fn get_overlay_style() -> container::Style {
+    container::Style {
+        text_color: Color::from_rgba8(255, 255, 255, 1.0).into(),
+        background: Some(Color::from_rgba8(0, 0, 0, 0.6).into()),
+        ..container::Style::default()
+    }
+}
+
+fn get_gallery_tile<'a>(gallery: &'a Gallery, index: usize, item: &'a GalleryItem) -> Element<'a, Message> {
+    let preview: Element<Message> = match gallery.thumbnails.get(&item.path) {
+        Some(handle) => image(handle)
+            .content_fit(ContentFit::Cover)
+            .width(Length::Fixed(THUMBNAIL_WIDTH))
+            .height(Length::Fixed(THUMBNAIL_HEIGHT))
+            .into(),
+        None => container(text("Loading...").size(15))
+            .center(Length::Fixed(THUMBNAIL_WIDTH))
+            .height(Length::Fixed(THUMBNAIL_HEIGHT))
+            .into(),
+    };
+
+    let mut tile = stack![preview];
+    if item.kind == GalleryKind::Video {
+        tile = tile.push(
+            container(container(get_icon(Icon::Play, 20.0)).padding(8).style(move |_| get_overlay_style()))
+                .padding(8)
+        );
+    }
+
+    button(tile)
+        .padding(0)
+        .on_press(Message::OpenGalleryItem(index))
+        .into()
+}
+
+fn get_gallery_grid(gallery: &Gallery) -> Element<Message> {
+    if gallery.items.is_empty() {
+        let dir = get_gallery_dir(&gallery.game).map_or_else(|| "the gallery folder".to_string(), |dir| dir.display().to_string());
+        return container(text(format!("No media yet, add images or videos to {dir}")).size(20))
+            .center(Length::Fill)
+            .into();
+    }
+
+    let rows = gallery.items.chunks(GRID_COLUMNS).enumerate().map(|(row_index, chunk)| {
+        row(chunk.iter().enumerate().map(|(column_index, item)| {
+            get_gallery_tile(gallery, row_index * GRID_COLUMNS + column_index, item)
+        }))
+        .spacing(10)
+        .into()
+    });
+
+    iced::widget::scrollable(Column::with_children(rows).spacing(10).padding(20).width(Length::Fill).align_x(Center))
+        .height(Length::Fill)
+        .into()
+}
+
+fn get_gallery_player<'a>(video: &'a Video, subtitle_track: Option<usize>, subtitle_text: Option<&'a str>) -> Element<'a, Message> {
+    let player = VideoPlayer::new(video)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .content_fit(ContentFit::Contain)
+        .on_end_of_stream(Message::GalleryVideoEnded)
+        .on_subtitle_text(Message::GallerySubtitleText);
+
+    let subtitles_label = match subtitle_track {
+        None => "Subtitles: off".to_string(),
+        Some(index) => {
+            let language = video.tracks(TrackKind::Subtitle).into_iter()
+                .find(|track| track.index == index)
+                .and_then(|track| track.language)
+                .unwrap_or_else(|| format!("#{}", index + 1));
+            format!("Subtitles: {language}")
+        }
+    };
+
+    let controls = container(row![
+        mouse_area(get_icon(if video.paused() {Icon::Play} else {Icon::Pause}, 25.0))
+            .on_release(Message::GalleryTogglePause),
+        SeekBar::new(video, Message::GalleryScrub).on_release(Message::GalleryScrubEnded),
+        get_icon(Icon::Volume, 20.0),
+        slider(0.0..=1.0, video.volume(), Message::GalleryVolumeChanged).step(0.01).width(Length::Fixed(120.0)),
+        mouse_area(text(subtitles_label).size(15)).on_release(Message::GalleryCycleSubtitles),
+    ].spacing(20).align_y(Center))
+    .padding(20)
+    .width(Length::Fill)
+    .style(move |_| get_overlay_style());
+
+    let subtitles: Element<Message> = match subtitle_text.filter(|_| subtitle_track.is_some()) {
+        Some(subtitle_text) => container(container(text(subtitle_text).size(22)).padding([4, 10]).style(move |_| get_overlay_style()))
+            .center_x(Length::Fill)
+            .padding(10)
+            .into(),
+        None => Space::new(Length::Fill, Length::Shrink).into(),
+    };
+
+    stack![
+        player,
+        column![Space::new(Length::Fill, Length::Fill), subtitles, controls]
+    ]
+    .into()
+}
+
+pub fn get_gallery_view(gallery: &Gallery) -> Element<Message> {
+    let (title, content): (String, Element<Message>) = match &gallery.viewer {
+        None => (format!("Gallery - {}", get_game_definition(&gallery.game).display_name), get_gallery_grid(gallery)),
+        Some(GalleryViewer::Image(handle)) => (
+            "Gallery".to_string(),
+            image(handle).content_fit(ContentFit::Contain).width(Length::Fill).height(Length::Fill).into(),
+        ),
+        Some(GalleryViewer::Loading(_)) => (
+            "Gallery".to_string(),
+            container(text("Loading...").size(20)).center(Length::Fill).into(),
+        ),
+        Some(GalleryViewer::Video { video, subtitle_track, subtitle_text }) => (
+            "Gallery".to_string(),
+            get_gallery_player(video, *subtitle_track, subtitle_text.as_deref()),
+        ),
+    };
+
+    let header = container(row![
+        mouse_area(get_icon(Icon::Back, 25.0))
+            .on_release(Message::GalleryBack),
+        text(title).size(25).font(Font::with_name("Montserrat-SemiBold")),
+    ].spacing(20).align_y(Center))
+    .padding(20)
+    .width(Length::Fill);
+
+    container(column![header, content])
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(move |_| container::Style {
+            text_color: Color::from_rgba8(255, 255, 255, 1.0).into(),
+            background: Some(Color::from_rgba8(10, 10, 10, 0.95).into()),
+            ..container::Style::default()
+        })
+        .into()
+}
@@ -0,0 +1,47 @@
+use std::{collections::HashSet, ffi::OsStr, path::Path};
+
+use iced::futures::channel::oneshot;
+use strum::IntoEnumIterator;
+use sysinfo::{ProcessesToUpdate, System};
+
+use crate::PossibleGames;
+
+use super::game_registry::get_game_definition;
+
+fn matches_process_name(candidate: &OsStr, name: &str) -> bool {
+    let candidate = candidate.to_string_lossy().to_lowercase();
+    let name = name.to_lowercase();
+    // linux truncates the process name to 15 characters
+    candidate == name || (candidate.len() == 15 && name.starts_with(&candidate))
+}
+
+/// Scans the system process list for running game clients, native or under wine.
+fn get_running_games() -> HashSet<PossibleGames> {
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+
+    PossibleGames::iter().filter(|game| {
+        let names = get_game_definition(game).process_names;
+        system.processes().values().any(|process| {
+            // wine processes are named after the loader, the windows path is the first argument
+            let exe_name = process.exe().and_then(Path::file_name);
+            let arg_name = process.cmd().first().and_then(|arg| {
+                arg.to_str().and_then(|arg| arg.rsplit(['\\', '/']).next()).map(OsStr::new)
+            });
+            names.iter().any(|name| {
+                matches_process_name(process.name(), name)
+                    || exe_name.is_some_and(|exe| matches_process_name(exe, name))
+                    || arg_name.is_some_and(|arg| matches_process_name(arg, name))
+            })
+        })
+    }).collect()
+}
+
+/// Runs the process scan on a thread, it reads the whole process list and would stall the executor.
+pub async fn poll_running_games() -> HashSet<PossibleGames> {
+    let (sender, receiver) = oneshot::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(get_running_games());
+    });
+    receiver.await.unwrap_or_default()
+}
@@ -0,0 +1,155 @@
+use strum::IntoEnumIterator;
+
+use crate::PossibleGames;
+
+/// Static facts about a supported game: how it is named, started and hosted.
+#[derive(Debug)]
+pub struct GameDefinition {
+    /// Identifier used in file names and links, e.g. `genshinimpact`.
+    pub slug: &'static str,
+    pub display_name: &'static str,
+    /// Client executable, relative to the game's install directory.
+    pub client_executable: &'static str,
+    /// Names of a running client process.
+    pub process_names: &'static [&'static str],
+    /// Server executable without its platform extension, relative to the server's install directory.
+    pub server_executable: &'static str,
+    pub default_server_port: u16,
+    pub server_config: ServerConfigDefinition,
+    /// Client log files, relative to the game's install directory, added to crash reports.
+    pub client_log_files: &'static [&'static str],
+    /// Server log files, relative to the server's install directory, added to crash reports.
+    pub server_log_files: &'static [&'static str],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFieldKind {
+    Text,
+    Port,
+    Integer { min: i64, max: i64 },
+    Bool,
+}
+
+/// A setting shown in the config editor's form.
+#[derive(Debug)]
+pub struct ConfigField {
+    /// Dotted path into the config document, e.g. `network.port`.
+    pub key: &'static str,
+    pub label: &'static str,
+    pub kind: ConfigFieldKind,
+    /// Written when the field is left empty.
+    pub default: &'static str,
+}
+
+/// Where a server keeps its config and which of its settings the editor knows about.
+#[derive(Debug)]
+pub struct ServerConfigDefinition {
+    /// Config file, relative to the server's install directory.
+    pub path: &'static str,
+    pub format: ConfigFormat,
+    pub fields: &'static [ConfigField],
+}
+
+static WUTHERING_WAVES: GameDefinition = GameDefinition {
+    slug: "wutheringwaves",
+    display_name: "Wuthering Waves",
+    client_executable: "Wuthering Waves.exe",
+    process_names: &["Client-Win64-Shipping.exe"],
+    server_executable: "wutheringwaves-server",
+    default_server_port: 5500,
+    server_config: ServerConfigDefinition {
+        path: "config.toml",
+        format: ConfigFormat::Toml,
+        fields: &[
+            ConfigField { key: "network.host", label: "Listen address", kind: ConfigFieldKind::Text, default: "0.0.0.0" },
+            ConfigField { key: "network.port", label: "Port", kind: ConfigFieldKind::Port, default: "5500" },
+            ConfigField { key: "gameplay.max_players", label: "Max players", kind: ConfigFieldKind::Integer { min: 1, max: 1000 }, default: "50" },
+            ConfigField { key: "debug.log_packets", label: "Log packets", kind: ConfigFieldKind::Bool, default: "false" },
+        ],
+    },
+    client_log_files: &["Client/Saved/Logs/Client.log"],
+    server_log_files: &["logs/latest.log"],
+};
+
+static ZENLESS_ZONE_ZERO: GameDefinition = GameDefinition {
+    slug: "zenlesszonezero",
+    display_name: "Zenless Zone Zero",
+    client_executable: "ZenlessZoneZero.exe",
+    process_names: &["ZenlessZoneZero.exe"],
+    server_executable: "zenlesszonezero-server",
+    default_server_port: 10301,
+    server_config: ServerConfigDefinition {
+        path: "config.json",
+        format: ConfigFormat::Json,
+        fields: &[
+            ConfigField { key: "gateway.host", label: "Listen address", kind: ConfigFieldKind::Text, default: "0.0.0.0" },
+            ConfigField { key: "gateway.port", label: "Port", kind: ConfigFieldKind::Port, default: "10301" },
+            ConfigField { key: "http.port", label: "Dispatch port", kind: ConfigFieldKind::Port, default: "10100" },
+            ConfigField { key: "debug", label: "Debug mode", kind: ConfigFieldKind::Bool, default: "false" },
+        ],
+    },
+    client_log_files: &["ZenlessZoneZero_Data/Player.log", "ZenlessZoneZero_Data/Player-prev.log"],
+    server_log_files: &["logs/latest.log"],
+};
+
+static HONKAI_STAR_RAIL: GameDefinition = GameDefinition {
+    slug: "honkaistarrail",
+    display_name: "Honkai: Star Rail",
+    client_executable: "StarRail.exe",
+    process_names: &["StarRail.exe"],
+    server_executable: "honkaistarrail-server",
+    default_server_port: 23301,
+    server_config: ServerConfigDefinition {
+        path: "config.json",
+        format: ConfigFormat::Json,
+        fields: &[
+            ConfigField { key: "gameserver.host", label: "Listen address", kind: ConfigFieldKind::Text, default: "0.0.0.0" },
+            ConfigField { key: "gameserver.port", label: "Port", kind: ConfigFieldKind::Port, default: "23301" },
+            ConfigField { key: "sdkserver.port", label: "SDK port", kind: ConfigFieldKind::Port, default: "21000" },
+            ConfigField { key: "player.start_level", label: "Starting level", kind: ConfigFieldKind::Integer { min: 1, max: 70 }, default: "1" },
+        ],
+    },
+    client_log_files: &["StarRail_Data/Player.log", "StarRail_Data/Player-prev.log"],
+    server_log_files: &["logs/latest.log"],
+};
+
+static GENSHIN_IMPACT: GameDefinition = GameDefinition {
+    slug: "genshinimpact",
+    display_name: "Genshin Impact",
+    client_executable: "GenshinImpact.exe",
+    process_names: &["GenshinImpact.exe", "YuanShen.exe"],
+    server_executable: "genshinimpact-server",
+    default_server_port: 22102,
+    server_config: ServerConfigDefinition {
+        path: "config.yaml",
+        format: ConfigFormat::Yaml,
+        fields: &[
+            ConfigField { key: "server.bind_address", label: "Listen address", kind: ConfigFieldKind::Text, default: "0.0.0.0" },
+            ConfigField { key: "server.game_port", label: "Port", kind: ConfigFieldKind::Port, default: "22102" },
+            ConfigField { key: "server.dispatch_port", label: "Dispatch port", kind: ConfigFieldKind::Port, default: "443" },
+            ConfigField { key: "account.auto_create", label: "Create accounts on login", kind: ConfigFieldKind::Bool, default: "true" },
+        ],
+    },
+    client_log_files: &["GenshinImpact_Data/output_log.txt", "YuanShen_Data/output_log.txt"],
+    server_log_files: &["logs/latest.log"],
+};
+
+pub fn get_game_definition(game: &PossibleGames) -> &'static GameDefinition {
+    match game {
+        PossibleGames::WutheringWaves => &WUTHERING_WAVES,
+        PossibleGames::ZenlessZoneZero => &ZENLESS_ZONE_ZERO,
+        PossibleGames::HonkaiStarRail => &HONKAI_STAR_RAIL,
+        PossibleGames::GenshinImpact => &GENSHIN_IMPACT,
+    }
+}
+
+pub fn get_game_by_slug(slug: &str) -> Option<PossibleGames> {
+    PossibleGames::iter().find(|game| get_game_definition(game).slug.eq_ignore_ascii_case(slug))
+}
@@ -0,0 +1,185 @@
+use std::{
+    io::{BufRead, BufReader, Read}, path::Path, process::{Command, Stdio}, thread, time::{Duration, Instant}
+};
+
+use iced::futures::channel::oneshot;
+use serde::{Deserialize, Serialize};
+
+use crate::PossibleGames;
+
+use super::{game_registry::get_game_definition, profiles::LaunchProfile};
+
+// how long the output of a finished hook gets to drain before the readers are left behind
+const OUTPUT_GRACE: Duration = Duration::from_millis(500);
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+fn default_abort_on_failure() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HookRepr {
+    Command(String),
+    Full {
+        command: String,
+        #[serde(default = "default_timeout_secs")]
+        timeout_secs: u64,
+        #[serde(default = "default_abort_on_failure")]
+        abort_on_failure: bool,
+    },
+}
+
+/// A shell command run around a launch. Also reads from a plain string, which gets the default timeout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "HookRepr")]
+pub struct Hook {
+    /// Run through `sh -c`, or `cmd /C` on windows, so it can be a script, an executable or a one-liner.
+    pub command: String,
+    /// Killed after this long and counted as failed.
+    pub timeout_secs: u64,
+    /// Whether a failing pre-launch hook keeps the game from starting, ignored after exit.
+    pub abort_on_failure: bool,
+}
+
+impl Hook {
+    pub fn new(command: String) -> Self {
+        Hook { command, timeout_secs: default_timeout_secs(), abort_on_failure: default_abort_on_failure() }
+    }
+}
+
+impl From<HookRepr> for Hook {
+    fn from(repr: HookRepr) -> Self {
+        match repr {
+            HookRepr::Command(command) => Hook::new(command),
+            HookRepr::Full { command, timeout_secs, abort_on_failure } => Hook { command, timeout_secs, abort_on_failure },
+        }
+    }
+}
+
+/// Hooks of a game, run for every launch around the ones of the selected profile.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameHooks {
+    pub pre_launch: Option<Hook>,
+    pub post_exit: Option<Hook>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookStage {
+    PreLaunch,
+    PostExit,
+}
+
+impl HookStage {
+    fn name(self) -> &'static str {
+        match self {
+            HookStage::PreLaunch => "pre-launch",
+            HookStage::PostExit => "post-exit",
+        }
+    }
+}
+
+/// Variables describing the launch, set for every hook of it.
+pub fn get_hook_env(game: &PossibleGames, install_dir: &Path, profile: Option<&LaunchProfile>, server: Option<&(String, u16)>) -> Vec<(String, String)> {
+    let definition = get_game_definition(game);
+    let mut env = vec![
+        ("RR_GAME".to_string(), definition.slug.to_string()),
+        ("RR_GAME_NAME".to_string(), definition.display_name.to_string()),
+        ("RR_INSTALL_DIR".to_string(), install_dir.display().to_string()),
+        ("RR_PROFILE".to_string(), profile.map(|profile| profile.name.clone()).unwrap_or_default()),
+    ];
+    if let Some((host, port)) = server {
+        env.push(("RR_SERVER_HOST".to_string(), host.clone()));
+        env.push(("RR_SERVER_PORT".to_string(), port.to_string()));
+    }
+    env
+}
+
+fn get_shell_command(command: &str) -> Command {
+    if cfg!(target_os = "windows") {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").arg(command);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
+    }
+}
+
+fn forward_output(pipe: impl Read + Send + 'static, prefix: String) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        for line in BufReader::new(pipe).split(b'\n').map_while(Result::ok) {
+            tracing::info!("{prefix} {}", String::from_utf8_lossy(&line).trim_end());
+        }
+    })
+}
+
+/// Runs one hook to completion or its timeout, with its output going to the launcher log.
+pub(crate) fn run_hook(hook: &Hook, stage: HookStage, env: &[(String, String)]) -> Result<(), String> {
+    let prefix = format!("[{} hook]", stage.name());
+    tracing::info!("{prefix} running `{}`", hook.command);
+
+    let mut child = get_shell_command(&hook.command)
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .env("RR_HOOK", stage.name())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("failed to start `{}`: {err}", hook.command))?;
+
+    let readers = [
+        child.stdout.take().map(|stdout| forward_output(stdout, prefix.clone())),
+        child.stderr.take().map(|stderr| forward_output(stderr, prefix.clone())),
+    ];
+
+    let deadline = Instant::now() + Duration::from_secs(hook.timeout_secs);
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Ok(status),
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break Err(format!("`{}` timed out after {}s", hook.command, hook.timeout_secs));
+            },
+            Ok(None) => thread::sleep(Duration::from_millis(50)),
+            Err(err) => break Err(err.to_string()),
+        }
+    };
+    // a process the hook started in the background, like `proxy &`, keeps the pipes open after the hook exits,
+    // so the readers only get a moment to catch up and are left to finish on their own
+    let grace = Instant::now() + OUTPUT_GRACE;
+    while readers.iter().flatten().any(|reader| !reader.is_finished()) && Instant::now() < grace {
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let status = status?;
+    tracing::info!("{prefix} `{}` exited with {status}", hook.command);
+    match status.success() {
+        true => Ok(()),
+        false => Err(format!("`{}` exited with {status}", hook.command)),
+    }
+}
+
+/// Runs hooks in order on a thread. A failing pre-launch hook that aborts on failure stops the rest and is returned,
+/// other failures are only logged.
+pub async fn run_hooks(hooks: Vec<Hook>, stage: HookStage, env: Vec<(String, String)>) -> Result<(), String> {
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        let result = hooks.iter().try_for_each(|hook| match run_hook(hook, stage, &env) {
+            Err(err) if stage == HookStage::PreLaunch && hook.abort_on_failure => Err(err),
+            Err(err) => {
+                tracing::warn!("[{} hook] {err}, continuing", stage.name());
+                Ok(())
+            },
+            Ok(()) => Ok(()),
+        });
+        let _ = sender.send(result);
+    });
+    receiver.await.unwrap_or_else(|_| Err("the hooks stopped unexpectedly".to_string()))
+}
@@ -0,0 +1,38 @@
+use iced::{widget::svg, Color, Element, Length};
+
+use crate::Message;
+
+/// Vector icons, drawn in white and tinted through the svg style so they render on every platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Icon {
+    Minimize,
+    Maximize,
+    Restore,
+    Close,
+    Back,
+    Play,
+    Pause,
+    Volume,
+}
+
+pub fn get_icon_handle(icon: Icon) -> svg::Handle {
+    let data: &'static [u8] = match icon {
+        Icon::Minimize => br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M3 8.5h10" stroke="white" stroke-width="1.2"/></svg>"#,
+        Icon::Maximize => br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><rect x="3.5" y="3.5" width="9" height="9" fill="none" stroke="white" stroke-width="1.2"/></svg>"#,
+        Icon::Restore => br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><rect x="3.5" y="5.5" width="7" height="7" fill="none" stroke="white" stroke-width="1.2"/><path d="M5.5 5.5v-2h7v7h-2" fill="none" stroke="white" stroke-width="1.2"/></svg>"#,
+        Icon::Close => br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M4 4l8 8M12 4l-8 8" stroke="white" stroke-width="1.2"/></svg>"#,
+        Icon::Back => br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M13 8H3.5M7.5 4l-4 4 4 4" fill="none" stroke="white" stroke-width="1.2"/></svg>"#,
+        Icon::Play => br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M4.5 3v10l8.5-5z" fill="white"/></svg>"#,
+        Icon::Pause => br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M4 3h3v10H4zM9 3h3v10H9z" fill="white"/></svg>"#,
+        Icon::Volume => br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M2.5 6h2.5l3.5-3v10L5 10H2.5z" fill="white"/><path d="M10.5 5.5a3.5 3.5 0 0 1 0 5M12 3.5a6 6 0 0 1 0 9" fill="none" stroke="white" stroke-width="1.2"/></svg>"#,
+    };
+    svg::Handle::from_memory(data)
+}
+
+pub fn get_icon<'a>(icon: Icon, size: f32) -> Element<'a, Message> {
+    svg(get_icon_handle(icon))
+        .width(Length::Fixed(size))
+        .height(Length::Fixed(size))
+        .style(move |_, _| svg::Style { color: Some(Color::WHITE) })
+        .into()
+}
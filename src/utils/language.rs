@@ -0,0 +1,70 @@
+use std::{env, fmt};
+
+/// Languages the launcher can prefer: ISO 639-1 code, name, then the ISO 639-2 codes (bibliographic and terminological)
+/// that stream tags use for it.
+const LANGUAGES: &[(&str, &str, &[&str])] = &[
+    ("en", "English", &["eng"]),
+    ("ja", "Japanese", &["jpn"]),
+    ("zh", "Chinese", &["chi", "zho"]),
+    ("ko", "Korean", &["kor"]),
+    ("de", "German", &["ger", "deu"]),
+    ("fr", "French", &["fre", "fra"]),
+    ("es", "Spanish", &["spa"]),
+    ("pt", "Portuguese", &["por"]),
+    ("it", "Italian", &["ita"]),
+    ("ru", "Russian", &["rus"]),
+    ("pl", "Polish", &["pol"]),
+    ("tr", "Turkish", &["tur"]),
+    ("th", "Thai", &["tha"]),
+    ("vi", "Vietnamese", &["vie"]),
+    ("id", "Indonesian", &["ind"]),
+    ("uk", "Ukrainian", &["ukr"]),
+];
+
+/// Maps a language tag like `de`, `ger`, `deu`, `de-AT` or `de_DE.UTF-8` to its ISO 639-1 code.
+pub fn normalize_language(tag: &str) -> Option<&'static str> {
+    let primary = tag.split(['-', '_', '.', '@']).next()?.trim().to_lowercase();
+    LANGUAGES.iter()
+        .find(|(code, _, aliases)| *code == primary || aliases.contains(&primary.as_str()))
+        .map(|(code, _, _)| *code)
+}
+
+fn get_system_language() -> Option<&'static str> {
+    // LANG looks like "en_US.UTF-8", the more specific variables win over it
+    ["LC_ALL", "LC_MESSAGES", "LANG"].into_iter()
+        .filter_map(|key| env::var(key).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| normalize_language(&value))
+}
+
+/// The language set in the launcher, or the system's when none is.
+pub fn get_preferred_language(setting: Option<&str>) -> Option<&'static str> {
+    match setting {
+        Some(code) => normalize_language(code),
+        None => get_system_language(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LanguageChoice {
+    System,
+    Language(&'static str),
+}
+
+impl fmt::Display for LanguageChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LanguageChoice::System => write!(f, "System language"),
+            LanguageChoice::Language(code) => {
+                let name = LANGUAGES.iter().find(|(other, _, _)| other == code).map_or(*code, |(_, name, _)| *name);
+                write!(f, "{name}")
+            },
+        }
+    }
+}
+
+pub fn get_language_choices() -> Vec<LanguageChoice> {
+    std::iter::once(LanguageChoice::System)
+        .chain(LANGUAGES.iter().map(|(code, _, _)| LanguageChoice::Language(*code)))
+        .collect()
+}
@@ -0,0 +1,133 @@
+use std::{
+    collections::VecDeque, io, sync::{Mutex, OnceLock}
+};
+
+use iced::{
+    widget::{column, mouse_area, row, scrollable, text, text_input, Column},
+    Alignment::Center, Color, Element, Font, Length
+};
+use tracing_appender::{non_blocking::WorkerGuard, rolling::{RollingFileAppender, Rotation}};
+use tracing_subscriber::{fmt, layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry};
+
+use crate::Message;
+
+use super::{icons::{get_icon, Icon}, server_log::get_log_dir, widgets::{get_panel, get_small_button}};
+
+/// Used when neither `RR_LOG` nor the saved state set a filter. The renderer is chatty at info.
+pub const DEFAULT_LOG_FILTER: &str = "info,wgpu_core=warn,wgpu_hal=warn,naga=warn,iced_wgpu=warn";
+/// Lines kept for the diagnostics view.
+const RECENT_LINES: usize = 1000;
+// a week of daily files
+const MAX_LOG_FILES: usize = 7;
+
+static RECENT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Writer that keeps the latest formatted lines in memory for the diagnostics view.
+struct RecentWriter;
+
+impl io::Write for RecentWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Ok(mut recent) = RECENT.lock() {
+            for line in String::from_utf8_lossy(buf).lines().filter(|line| !line.is_empty()) {
+                if recent.len() == RECENT_LINES {
+                    recent.pop_front();
+                }
+                recent.push_back(line.to_string());
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Sets up logging to stderr, daily rotated `launcher.<date>.log` files in the log directory and the diagnostics view.
+/// `RR_LOG` overrides `filter`, both use `EnvFilter` syntax, e.g. `info,reversed_rooms_launcher::utils::supervisor=debug`.
+/// Records of the `log` crate, like the video player's, are forwarded too. Keep the guard alive until exit so the file gets flushed.
+pub fn init_logging(filter: &str) -> Option<WorkerGuard> {
+    let filter = std::env::var("RR_LOG").ok()
+        .and_then(|filter| EnvFilter::try_new(filter).ok())
+        .or_else(|| EnvFilter::try_new(filter).ok())
+        .unwrap_or_else(|| EnvFilter::new(DEFAULT_LOG_FILTER));
+    let (filter, handle) = reload::Layer::new(filter);
+    let _ = FILTER_HANDLE.set(handle);
+
+    let appender = get_log_dir().and_then(|dir| {
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix("launcher")
+            .filename_suffix("log")
+            .max_log_files(MAX_LOG_FILES)
+            .build(dir)
+            .ok()
+    });
+    let (file_layer, guard) = match appender {
+        Some(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            (Some(fmt::layer().with_ansi(false).with_writer(writer)), Some(guard))
+        },
+        None => (None, None),
+    };
+
+    let result = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_writer(io::stderr))
+        .with(file_layer)
+        .with(fmt::layer().with_ansi(false).without_time().with_writer(|| RecentWriter))
+        .try_init();
+    if let Err(err) = result {
+        eprintln!("Failed to set up logging: {err}");
+    }
+    guard
+}
+
+/// Swaps the active filter, e.g. from the diagnostics view.
+pub fn set_log_filter(filter: &str) -> Result<(), String> {
+    let filter = EnvFilter::try_new(filter).map_err(|err| err.to_string())?;
+    let handle = FILTER_HANDLE.get().ok_or("Logging is not set up")?;
+    handle.reload(filter).map_err(|err| err.to_string())
+}
+
+pub fn get_recent_log_lines() -> Vec<String> {
+    RECENT.lock().map(|recent| recent.iter().cloned().collect()).unwrap_or_default()
+}
+
+/// State of the hidden diagnostics view, opened with F12.
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    pub lines: Vec<String>,
+    /// Filter being edited, applied with the button.
+    pub filter: String,
+    pub error: Option<String>,
+}
+
+pub fn get_diagnostics_view(diagnostics: &Diagnostics) -> Element<Message> {
+    let header = row![
+        mouse_area(get_icon(Icon::Back, 25.0))
+            .on_release(Message::ToggleDiagnostics),
+        text("Diagnostics").size(25).font(Font::with_name("Montserrat-SemiBold")).width(Length::Fill),
+        get_small_button("Open log folder", Some(Message::OpenLogFolder)),
+    ].spacing(20).align_y(Center);
+
+    let mut filter_row = row![
+        text_input(DEFAULT_LOG_FILTER, &diagnostics.filter)
+            .on_input(Message::LogFilterChanged)
+            .on_submit(Message::ApplyLogFilter)
+            .padding(8),
+        get_small_button("Apply filter", Some(Message::ApplyLogFilter)),
+    ].spacing(10).align_y(Center);
+    if let Some(err) = &diagnostics.error {
+        filter_row = filter_row.push(text(err).size(13).color(Color::from_rgb8(240, 100, 100)));
+    }
+
+    let lines = Column::with_children(diagnostics.lines.iter().map(|line| text(line).size(12).font(Font::MONOSPACE).into()));
+
+    get_panel(column![
+        header,
+        filter_row,
+        scrollable(lines.width(Length::Fill)).anchor_bottom().height(Length::Fill),
+    ].spacing(15))
+}
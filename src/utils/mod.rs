@@ -1,2 +1,26 @@
+pub mod crash_report;
+pub mod debug_overlay;
+pub mod deep_link;
+pub mod file_manager;
+pub mod gallery;
+pub mod game_process;
+pub mod game_registry;
+pub mod hooks;
+pub mod icons;
 pub mod img_utils;
-pub mod visual_helper;
\ No newline at end of file
+pub mod language;
+pub mod logging;
+pub mod paths;
+pub mod profiles;
+pub mod runner;
+pub mod server_config;
+pub mod server_list;
+pub mod server_log;
+pub mod sessions;
+pub mod settings;
+pub mod single_instance;
+pub mod supervisor;
+pub mod title_bar;
+pub mod tray;
+pub mod visual_helper;
+pub mod widgets;
\ No newline at end of file
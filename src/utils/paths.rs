@@ -0,0 +1,9 @@
+use std::path::PathBuf;
+
+use directories::ProjectDirs;
+
+/// Everything the launcher writes lives under this directory, e.g. `~/.local/share/reversed-rooms-launcher/`.
+pub fn get_data_dir() -> Option<PathBuf> {
+    let dirs = ProjectDirs::from("", "", "reversed-rooms-launcher")?;
+    Some(dirs.data_dir().to_path_buf())
+}
@@ -0,0 +1,106 @@
+use std::{collections::BTreeMap, fmt, fs, io, path::PathBuf, process::Command};
+
+use serde::{Deserialize, Serialize};
+
+use crate::PossibleGames;
+
+use super::{game_registry::get_game_definition, hooks::Hook, paths::get_data_dir};
+
+/// A named way to start a game, e.g. against a local dev server or a shared team server with another client build.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LaunchProfile {
+    pub name: String,
+    pub game: PossibleGames,
+    /// Client install to start instead of the game's default one.
+    pub client_install: Option<PathBuf>,
+    /// Server the client should connect to, handed over as `RR_SERVER_HOST` and `RR_SERVER_PORT`.
+    pub server_host: Option<String>,
+    pub server_port: Option<u16>,
+    pub extra_args: Vec<String>,
+    pub env: BTreeMap<String, String>,
+    /// Run before the client starts, after the game's own pre-launch hook.
+    pub pre_launch_hook: Option<Hook>,
+    /// Run after the client exits, before the game's own post-exit hook.
+    pub post_exit_hook: Option<Hook>,
+}
+
+impl LaunchProfile {
+    /// Adds the profile's arguments, environment and server target to a client command.
+    pub fn apply(&self, command: &mut Command) {
+        command.args(&self.extra_args);
+        command.envs(&self.env);
+        if let Some(host) = &self.server_host {
+            command.env("RR_SERVER_HOST", host);
+        }
+        if let Some(port) = self.server_port {
+            command.env("RR_SERVER_PORT", port.to_string());
+        }
+    }
+}
+
+/// Entry of the profile dropdown next to the Launch button.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileChoice {
+    Default,
+    Named(String),
+}
+
+impl fmt::Display for ProfileChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileChoice::Default => write!(f, "Default"),
+            ProfileChoice::Named(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+pub fn get_profile_choices(profiles: &[LaunchProfile]) -> Vec<ProfileChoice> {
+    std::iter::once(ProfileChoice::Default)
+        .chain(profiles.iter().map(|profile| ProfileChoice::Named(profile.name.clone())))
+        .collect()
+}
+
+/// Exported profiles live in `<data dir>/profiles/<game>/`, one json file per profile.
+pub fn get_profiles_dir(game: &PossibleGames) -> Option<PathBuf> {
+    Some(get_data_dir()?.join("profiles").join(get_game_definition(game).slug))
+}
+
+fn get_profile_file_name(name: &str) -> String {
+    let name: String = name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' {c} else {'-'}).collect();
+    format!("{name}.json")
+}
+
+pub fn export_profile(profile: &LaunchProfile) -> io::Result<PathBuf> {
+    let dir = get_profiles_dir(&profile.game).ok_or(io::ErrorKind::NotFound)?;
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(get_profile_file_name(&profile.name));
+    let json = serde_json::to_string_pretty(profile).map_err(io::Error::other)?;
+    fs::write(&path, json)?;
+    Ok(path)
+}
+
+/// Reads every profile file of a game, skipping files that are not valid profiles for it.
+pub fn import_profiles(game: &PossibleGames) -> Vec<LaunchProfile> {
+    let Some(entries) = get_profiles_dir(game).and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+
+    let mut profiles: Vec<LaunchProfile> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .filter_map(|path| {
+            let profile = fs::read_to_string(&path).ok()
+                .and_then(|contents| serde_json::from_str::<LaunchProfile>(&contents).ok())
+                .filter(|profile| !profile.name.trim().is_empty() && profile.game == *game);
+            if profile.is_none() {
+                tracing::warn!("Skipping invalid launch profile {}", path.display());
+            }
+            profile
+        })
+        .collect();
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    profiles
+}
@@ -0,0 +1,325 @@
+use std::{
+    collections::BTreeMap, env, fmt, fs, io, path::{Path, PathBuf}, process::{Command, Stdio}
+};
+
+use directories::BaseDirs;
+use iced::{
+    futures::channel::oneshot, widget::{checkbox, column, mouse_area, pick_list, row, text, text_input},
+    Alignment::Center, Color, Element, Font, Length
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{Message, PossibleGames};
+
+use super::{game_registry::get_game_definition, icons::{get_icon, Icon}, paths::get_data_dir, widgets::{get_panel, get_small_button}};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RunnerKind {
+    Wine,
+    Proton,
+}
+
+/// An installed compatibility layer: a `wine` binary or a Proton build's `proton` script.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Runner {
+    pub kind: RunnerKind,
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl fmt::Display for Runner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// How a game's windows client is run on linux, `runner: None` starts it directly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunnerSettings {
+    pub runner: Option<Runner>,
+    /// Only honored by Proton, which ships dxvk, a plain wine prefix doesn't have its dlls.
+    pub dxvk: bool,
+    /// `WINEDLLOVERRIDES` entries, e.g. `version` -> `n,b`.
+    pub dll_overrides: BTreeMap<String, String>,
+    pub env: BTreeMap<String, String>,
+}
+
+impl Default for RunnerSettings {
+    fn default() -> Self {
+        RunnerSettings { runner: None, dxvk: true, dll_overrides: BTreeMap::new(), env: BTreeMap::new() }
+    }
+}
+
+impl RunnerSettings {
+    /// The `WINEDLLOVERRIDES` value.
+    pub fn get_dll_overrides(&self) -> String {
+        format_pairs(&self.dll_overrides, ";")
+    }
+}
+
+/// Parses `dll=mode` pairs separated by `;` or whitespace, e.g. `version=n,b; d3dcompiler_47=n`.
+pub fn parse_dll_overrides(text: &str) -> Result<BTreeMap<String, String>, String> {
+    parse_pairs(text, |c| c == ';' || c.is_whitespace(), "dll=mode")
+}
+
+/// Parses `KEY=VALUE` pairs separated by whitespace, e.g. `DXVK_HUD=fps WINEDEBUG=-all`.
+pub fn parse_env(text: &str) -> Result<BTreeMap<String, String>, String> {
+    parse_pairs(text, char::is_whitespace, "KEY=VALUE")
+}
+
+fn parse_pairs(text: &str, separator: impl Fn(char) -> bool, expected: &str) -> Result<BTreeMap<String, String>, String> {
+    text.split(separator)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+            _ => Err(format!("\"{pair}\" is not {expected}")),
+        })
+        .collect()
+}
+
+pub fn format_pairs(pairs: &BTreeMap<String, String>, separator: &str) -> String {
+    pairs.iter().map(|(key, value)| format!("{key}={value}")).collect::<Vec<_>>().join(separator)
+}
+
+/// Finds `wine` in the given directories, the first match wins like it would on `PATH`.
+pub fn discover_wine_in(dirs: impl IntoIterator<Item = PathBuf>) -> Option<Runner> {
+    dirs.into_iter()
+        .map(|dir| dir.join("wine"))
+        .find(|path| path.is_file())
+        .map(|path| Runner { kind: RunnerKind::Wine, name: format!("Wine ({})", path.display()), path })
+}
+
+/// Finds Proton builds, each a directory with a `proton` script, directly inside the given directories.
+pub fn discover_proton_in(dirs: impl IntoIterator<Item = PathBuf>) -> Vec<Runner> {
+    let mut runners: Vec<Runner> = dirs.into_iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.filter_map(Result::ok))
+        .map(|entry| entry.path())
+        .filter(|path| path.join("proton").is_file())
+        .map(|path| Runner {
+            kind: RunnerKind::Proton,
+            name: path.file_name().unwrap_or_default().to_string_lossy().into_owned(),
+            path: path.join("proton"),
+        })
+        .collect();
+    runners.sort_by(|a, b| a.name.cmp(&b.name));
+    runners
+}
+
+/// Wine from `PATH`, Lutris' wine builds, and Proton from Steam's library and compatibility tools.
+pub fn discover_runners() -> Vec<Runner> {
+    let mut runners = Vec::new();
+    if let Some(path) = env::var_os("PATH") {
+        runners.extend(discover_wine_in(env::split_paths(&path)));
+    }
+
+    let Some(dirs) = BaseDirs::new() else {
+        return runners;
+    };
+    let lutris = dirs.data_dir().join("lutris/runners/wine");
+    if let Ok(entries) = fs::read_dir(lutris) {
+        runners.extend(entries.filter_map(Result::ok).filter_map(|entry| {
+            let path = entry.path().join("bin/wine");
+            path.is_file().then(|| Runner { kind: RunnerKind::Wine, name: entry.file_name().to_string_lossy().into_owned(), path })
+        }));
+    }
+
+    let steam_roots = [dirs.home_dir().join(".steam/root"), dirs.data_dir().join("Steam")];
+    let mut proton_dirs = Vec::new();
+    for root in steam_roots.iter().filter(|root| root.is_dir()) {
+        proton_dirs.push(root.join("steamapps/common"));
+        proton_dirs.push(root.join("compatibilitytools.d"));
+    }
+    for runner in discover_proton_in(proton_dirs) {
+        // both roots are usually the same install through a symlink
+        if !runners.iter().any(|existing| existing.name == runner.name) {
+            runners.push(runner);
+        }
+    }
+    runners
+}
+
+/// Each game gets its own prefix in `<data dir>/prefixes/<game>/`.
+pub fn get_prefix_dir(game: &PossibleGames) -> Option<PathBuf> {
+    Some(get_data_dir()?.join("prefixes").join(get_game_definition(game).slug))
+}
+
+fn set_prefix_env(command: &mut Command, runner: &Runner, prefix: &Path) {
+    match runner.kind {
+        RunnerKind::Wine => {
+            command.env("WINEPREFIX", prefix);
+        },
+        RunnerKind::Proton => {
+            // proton keeps the wine prefix in a `pfx` directory inside its compat data
+            command.env("STEAM_COMPAT_DATA_PATH", prefix);
+            if let Some(dirs) = BaseDirs::new() {
+                command.env("STEAM_COMPAT_CLIENT_INSTALL_PATH", dirs.home_dir().join(".steam/steam"));
+            }
+        },
+    }
+}
+
+fn get_runner_command(runner: &Runner) -> Command {
+    let mut command = Command::new(&runner.path);
+    if runner.kind == RunnerKind::Proton {
+        command.arg("run");
+    }
+    command
+}
+
+/// Rewrites a windows command to run through `runner` in `prefix`, keeping its arguments, environment and working directory.
+pub fn wrap_command(command: &Command, runner: &Runner, settings: &RunnerSettings, prefix: &Path) -> Command {
+    let mut wrapped = get_runner_command(runner);
+    wrapped.arg(command.get_program()).args(command.get_args());
+    for (key, value) in command.get_envs() {
+        match value {
+            Some(value) => wrapped.env(key, value),
+            None => wrapped.env_remove(key),
+        };
+    }
+    if let Some(dir) = command.get_current_dir() {
+        wrapped.current_dir(dir);
+    }
+
+    set_prefix_env(&mut wrapped, runner, prefix);
+    let overrides = settings.get_dll_overrides();
+    if !overrides.is_empty() {
+        wrapped.env("WINEDLLOVERRIDES", overrides);
+    }
+    if runner.kind == RunnerKind::Proton && !settings.dxvk {
+        wrapped.env("PROTON_USE_WINED3D", "1");
+    }
+    wrapped.envs(&settings.env);
+    wrapped
+}
+
+/// Initializes a prefix with `wineboot --init`, blocking until the runner is done.
+pub fn create_prefix(runner: &Runner, prefix: &Path) -> io::Result<()> {
+    fs::create_dir_all(prefix)?;
+    let mut command = get_runner_command(runner);
+    command.args(["wineboot", "--init"]).stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+    set_prefix_env(&mut command, runner, prefix);
+
+    let status = command.status()?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(io::Error::other(format!("wineboot exited with {status}"))),
+    }
+}
+
+/// Deletes a prefix and creates it again from scratch.
+pub fn reset_prefix(runner: &Runner, prefix: &Path) -> io::Result<()> {
+    match fs::remove_dir_all(prefix) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+        _ => {},
+    }
+    create_prefix(runner, prefix)
+}
+
+/// Runs [`create_prefix`] or [`reset_prefix`] on a thread, a fresh prefix takes a while to boot.
+pub async fn prepare_prefix(runner: Runner, prefix: PathBuf, reset: bool) -> Result<(), String> {
+    let (sender, receiver) = oneshot::channel();
+    std::thread::spawn(move || {
+        let result = if reset {reset_prefix(&runner, &prefix)} else {create_prefix(&runner, &prefix)};
+        let _ = sender.send(result.map_err(|err| err.to_string()));
+    });
+    receiver.await.unwrap_or_else(|_| Err("preparing the prefix stopped unexpectedly".to_string()))
+}
+
+/// Entry of the runner dropdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunnerChoice {
+    Native,
+    Runner(Runner),
+}
+
+impl fmt::Display for RunnerChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunnerChoice::Native => write!(f, "None, start the client directly"),
+            RunnerChoice::Runner(runner) => write!(f, "{runner}"),
+        }
+    }
+}
+
+/// Editing state of a game's runner settings, the text fields only reach the settings once they parse.
+#[derive(Debug)]
+pub struct RunnerPanel {
+    pub game: PossibleGames,
+    pub runners: Vec<Runner>,
+    pub dll_overrides: String,
+    pub env: String,
+    pub error: Option<String>,
+    // a prefix is being created or reset
+    pub busy: bool,
+}
+
+impl RunnerPanel {
+    pub fn new(game: PossibleGames, settings: &RunnerSettings) -> Self {
+        let mut runners = discover_runners();
+        // keep a configured runner selectable even if discovery no longer finds it
+        if let Some(runner) = &settings.runner && !runners.contains(runner) {
+            runners.push(runner.clone());
+        }
+        RunnerPanel {
+            game,
+            runners,
+            dll_overrides: format_pairs(&settings.dll_overrides, "; "),
+            env: format_pairs(&settings.env, " "),
+            error: None,
+            busy: false,
+        }
+    }
+}
+
+pub fn get_runner_view<'a>(panel: &'a RunnerPanel, settings: &RunnerSettings) -> Element<'a, Message> {
+    let choices: Vec<RunnerChoice> = std::iter::once(RunnerChoice::Native)
+        .chain(panel.runners.iter().cloned().map(RunnerChoice::Runner))
+        .collect();
+    let selected = settings.runner.clone().map_or(RunnerChoice::Native, RunnerChoice::Runner);
+    let prefix = get_prefix_dir(&panel.game);
+    let prefix_exists = prefix.as_ref().is_some_and(|prefix| prefix.is_dir());
+    let can_prepare = settings.runner.is_some() && !panel.busy;
+    let is_proton = settings.runner.as_ref().is_some_and(|runner| runner.kind == RunnerKind::Proton);
+
+    let header = row![
+        mouse_area(get_icon(Icon::Back, 25.0))
+            .on_release(Message::CloseRunnerSettings),
+        text(format!("Runner - {}", get_game_definition(&panel.game).display_name)).size(25).font(Font::with_name("Montserrat-SemiBold")),
+    ].spacing(20).align_y(Center);
+
+    let prefix_status = match (&prefix, panel.busy, prefix_exists) {
+        (None, _, _) => "No data directory for prefixes".to_string(),
+        (Some(_), true, _) => "Preparing the prefix...".to_string(),
+        (Some(prefix), false, true) => format!("Prefix: {}", prefix.display()),
+        (Some(prefix), false, false) => format!("Prefix: {} (not created yet)", prefix.display()),
+    };
+
+    let mut content = column![
+        header,
+        column![
+            text("Runner").size(15),
+            pick_list(choices, Some(selected), Message::RunnerSelected).padding(8),
+        ].spacing(5),
+        checkbox("DXVK, Direct3D through Vulkan (Proton only)", is_proton && settings.dxvk).on_toggle_maybe(is_proton.then_some(Message::ToggleDxvk)),
+        column![
+            text("DLL overrides").size(15),
+            text_input("version=n,b; d3dcompiler_47=n", &panel.dll_overrides).on_input(Message::DllOverridesChanged).padding(8),
+        ].spacing(5),
+        column![
+            text("Environment").size(15),
+            text_input("DXVK_HUD=fps WINEDEBUG=-all", &panel.env).on_input(Message::RunnerEnvChanged).padding(8),
+        ].spacing(5),
+        row![
+            text(prefix_status).size(14).width(Length::Fill),
+            get_small_button("Create prefix", (can_prepare && !prefix_exists).then_some(Message::PreparePrefix(false))),
+            get_small_button("Reset prefix", (can_prepare && prefix_exists).then_some(Message::PreparePrefix(true))),
+        ].spacing(10).align_y(Center),
+    ].spacing(20).max_width(700);
+    if let Some(err) = &panel.error {
+        content = content.push(text(err).size(14).color(Color::from_rgb8(240, 100, 100)));
+    }
+
+    get_panel(content)
+}
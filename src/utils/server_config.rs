@@ -0,0 +1,259 @@
+use std::{fs, path::{Path, PathBuf}, time::{SystemTime, UNIX_EPOCH}};
+
+use iced::{
+    widget::{checkbox, column, mouse_area, row, scrollable, text, text_editor, text_input, Column},
+    Alignment::Center, Color, Element, Font, Length
+};
+use serde_json::{Map, Value};
+
+use crate::{Message, PossibleGames};
+
+use super::{game_registry::{get_game_definition, ConfigField, ConfigFieldKind, ConfigFormat, ServerConfigDefinition}, icons::{get_icon, Icon}, widgets::{get_dialog_button, get_panel}};
+
+/// Editing state of a server's config file. The document keeps settings the form doesn't know about,
+/// so saving from the form only touches the fields of the registry schema.
+#[derive(Debug)]
+pub struct ConfigEditor {
+    pub game: PossibleGames,
+    pub path: PathBuf,
+    definition: &'static ServerConfigDefinition,
+    document: Value,
+    // text of every schema field, empty means its default
+    values: Vec<String>,
+    errors: Vec<Option<String>>,
+    // raw text mode while set
+    raw: Option<text_editor::Content>,
+}
+
+fn parse_config(format: ConfigFormat, contents: &str) -> Result<Value, String> {
+    if contents.trim().is_empty() {
+        return Ok(Value::Object(Map::new()));
+    }
+    let document: Value = match format {
+        ConfigFormat::Json => serde_json::from_str(contents).map_err(|err| err.to_string())?,
+        ConfigFormat::Toml => toml::from_str(contents).map_err(|err| err.to_string())?,
+        ConfigFormat::Yaml => serde_yaml::from_str(contents).map_err(|err| err.to_string())?,
+    };
+    match document {
+        Value::Object(_) => Ok(document),
+        _ => Err("The config is not a table of settings".to_string()),
+    }
+}
+
+fn serialize_config(format: ConfigFormat, document: &Value) -> Result<String, String> {
+    match format {
+        ConfigFormat::Json => serde_json::to_string_pretty(document).map_err(|err| err.to_string()),
+        ConfigFormat::Toml => toml::to_string_pretty(document).map_err(|err| err.to_string()),
+        ConfigFormat::Yaml => serde_yaml::to_string(document).map_err(|err| err.to_string()),
+    }
+}
+
+pub(crate) fn get_value<'a>(document: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.').try_fold(document, |value, part| value.get(part))
+}
+
+pub(crate) fn set_value(document: &mut Value, key: &str, new_value: Value) {
+    let mut value = document;
+    for part in key.split('.') {
+        if !value.is_object() {
+            *value = Value::Object(Map::new());
+        }
+        value = value.as_object_mut().unwrap().entry(part).or_insert(Value::Null);
+    }
+    *value = new_value;
+}
+
+fn value_to_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+/// Checks a field's text against its kind, falling back to the default for empty text.
+pub(crate) fn parse_field(field: &ConfigField, text: &str) -> Result<Value, String> {
+    let text = match text.trim() {
+        "" => field.default,
+        text => text,
+    };
+    match field.kind {
+        ConfigFieldKind::Text => Ok(Value::String(text.to_string())),
+        ConfigFieldKind::Port => text.parse::<u16>().ok()
+            .filter(|port| *port != 0)
+            .map(Value::from)
+            .ok_or_else(|| "Must be a port between 1 and 65535".to_string()),
+        ConfigFieldKind::Integer { min, max } => text.parse::<i64>().ok()
+            .filter(|number| (min..=max).contains(number))
+            .map(Value::from)
+            .ok_or_else(|| format!("Must be a whole number between {min} and {max}")),
+        ConfigFieldKind::Bool => text.parse::<bool>()
+            .map(Value::Bool)
+            .map_err(|_| "Must be true or false".to_string()),
+    }
+}
+
+impl ConfigEditor {
+    /// Opens the config of a game's server, a missing file starts out with every default.
+    pub fn open(game: &PossibleGames, server_dir: &Path) -> Result<Self, String> {
+        let definition = &get_game_definition(game).server_config;
+        let path = server_dir.join(definition.path);
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(format!("Failed to read {}: {err}", path.display())),
+        };
+
+        let mut editor = ConfigEditor {
+            game: game.clone(),
+            path,
+            definition,
+            document: Value::Null,
+            values: Vec::new(),
+            errors: Vec::new(),
+            raw: None,
+        };
+        editor.set_document(parse_config(definition.format, &contents)?);
+        Ok(editor)
+    }
+
+    fn set_document(&mut self, document: Value) {
+        self.values = self.definition.fields.iter()
+            .map(|field| get_value(&document, field.key).map(value_to_text).unwrap_or_default())
+            .collect();
+        self.errors = self.definition.fields.iter().zip(&self.values)
+            .map(|(field, value)| parse_field(field, value).err())
+            .collect();
+        self.document = document;
+    }
+
+    pub fn is_raw(&self) -> bool {
+        self.raw.is_some()
+    }
+
+    pub fn set_field(&mut self, index: usize, value: String) {
+        if let Some(field) = self.definition.fields.get(index) {
+            self.errors[index] = parse_field(field, &value).err();
+            self.values[index] = value;
+        }
+    }
+
+    pub fn edit_raw(&mut self, action: text_editor::Action) {
+        if let Some(raw) = self.raw.as_mut() {
+            raw.perform(action);
+        }
+    }
+
+    /// The document with the form applied, or the parsed raw text in raw mode.
+    fn build_document(&mut self) -> Result<Value, String> {
+        if let Some(raw) = self.raw.as_ref() {
+            let document = parse_config(self.definition.format, &raw.text())?;
+            self.set_document(document);
+        }
+
+        let mut document = self.document.clone();
+        for (index, field) in self.definition.fields.iter().enumerate() {
+            match parse_field(field, &self.values[index]) {
+                Ok(value) => set_value(&mut document, field.key, value),
+                Err(err) => self.errors[index] = Some(err),
+            }
+        }
+        match self.errors.iter().any(Option::is_some) {
+            true => Err("Some settings are invalid".to_string()),
+            false => Ok(document),
+        }
+    }
+
+    /// Switches between the form and the raw text, refusing to leave a mode that holds invalid settings.
+    pub fn toggle_raw(&mut self) -> Result<(), String> {
+        let document = self.build_document()?;
+        match self.raw.take() {
+            Some(_) => self.set_document(document),
+            None => self.raw = Some(text_editor::Content::with_text(&serialize_config(self.definition.format, &document)?)),
+        }
+        Ok(())
+    }
+
+    /// Writes the config, copying the previous file to `<name>.<unix time>.bak` first. Returns the backup, if there was a file to back up.
+    /// Raw text is validated but written as typed, so its comments and formatting survive.
+    pub fn save(&mut self) -> Result<Option<PathBuf>, String> {
+        let document = self.build_document()?;
+        let contents = match self.raw.as_ref() {
+            Some(raw) => raw.text(),
+            None => serialize_config(self.definition.format, &document)?,
+        };
+
+        let backup = match self.path.exists() {
+            true => {
+                let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or_default();
+                let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+                name.push(format!(".{timestamp}.bak"));
+                let backup = self.path.with_file_name(name);
+                fs::copy(&self.path, &backup).map_err(|err| format!("Failed to back up the config: {err}"))?;
+                Some(backup)
+            },
+            false => None,
+        };
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+        }
+        fs::write(&self.path, contents).map_err(|err| format!("Failed to write the config: {err}"))?;
+        self.set_document(document);
+        Ok(backup)
+    }
+}
+
+fn get_config_form(editor: &ConfigEditor) -> Element<Message> {
+    let fields = editor.definition.fields.iter().enumerate().map(|(index, field)| {
+        let value = &editor.values[index];
+        let input: Element<Message> = match field.kind {
+            ConfigFieldKind::Bool => {
+                let checked = parse_field(field, value).ok().and_then(|value| value.as_bool()).unwrap_or_default();
+                checkbox(field.label, checked)
+                    .on_toggle(move |checked| Message::ConfigFieldChanged(index, checked.to_string()))
+                    .into()
+            },
+            _ => column![
+                text(field.label).size(15),
+                text_input(field.default, value)
+                    .on_input(move |value| Message::ConfigFieldChanged(index, value))
+                    .padding(8),
+            ].spacing(5).into(),
+        };
+
+        let mut entry = column![input].spacing(4);
+        if let Some(err) = &editor.errors[index] {
+            entry = entry.push(text(err).size(13).color(Color::from_rgb8(240, 100, 100)));
+        }
+        entry.into()
+    });
+
+    scrollable(Column::with_children(fields).spacing(15).max_width(600))
+        .height(Length::Fill)
+        .into()
+}
+
+pub fn get_server_config_view(editor: &ConfigEditor) -> Element<Message> {
+    let content: Element<Message> = match editor.raw.as_ref() {
+        Some(raw) => text_editor(raw)
+            .on_action(Message::ConfigRawEdited)
+            .font(Font::MONOSPACE)
+            .height(Length::Fill)
+            .into(),
+        None => get_config_form(editor),
+    };
+
+    let header = row![
+        mouse_area(get_icon(Icon::Back, 25.0))
+            .on_release(Message::CloseServerConfig),
+        column![
+            text(format!("Server config - {}", get_game_definition(&editor.game).display_name)).size(25).font(Font::with_name("Montserrat-SemiBold")),
+            text(editor.path.display().to_string()).size(13).color(Color::from_rgba8(255, 255, 255, 0.6)),
+        ].width(Length::Fill),
+        get_dialog_button(if editor.is_raw() {"Form"} else {"Raw text"}, Message::ConfigToggleRaw, false),
+        get_dialog_button("Save", Message::SaveServerConfig, true),
+    ].spacing(20).align_y(Center);
+
+    get_panel(column![header, content].spacing(20))
+}
@@ -0,0 +1,275 @@
+use std::{collections::HashMap, io, net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket}, time::{Duration, Instant}};
+
+use iced::{
+    border, futures::channel::oneshot, widget::{column, container, mouse_area, row, scrollable, text, text_input, Column, Space},
+    Alignment::Center, Color, Element, Font, Length
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{Message, PossibleGames};
+
+use super::{game_registry::get_game_definition, icons::{get_icon, Icon}, widgets::{get_panel, get_small_button}};
+
+/// How often remote servers are checked while the launcher is visible.
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(15);
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ServerProtocol {
+    #[default]
+    Tcp,
+    Udp,
+}
+
+/// A server hosted somewhere else, added by hand or through a `rrlauncher://server/add` link.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteServer {
+    pub game: PossibleGames,
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    #[serde(default)]
+    pub region: Option<String>,
+    /// Protocol used for reachability checks.
+    #[serde(default)]
+    pub protocol: ServerProtocol,
+}
+
+impl RemoteServer {
+    /// `host:port`, also used as the key of the server's status and selection.
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+/// Server a game's client is pointed at when launching.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ServerChoice {
+    /// The locally installed server, started on launch if it isn't running.
+    Local,
+    /// A remote server by its address.
+    Remote(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerStatus {
+    Reachable(Duration),
+    // udp servers are allowed to ignore the probe
+    NoReply,
+    Unreachable(String),
+}
+
+/// Fields of the "add server" form.
+#[derive(Debug, Clone, Default)]
+pub struct ServerForm {
+    pub name: String,
+    pub host: String,
+    pub port: String,
+    pub region: String,
+    pub protocol: ServerProtocol,
+}
+
+impl ServerForm {
+    pub fn to_server(&self, game: &PossibleGames) -> Result<RemoteServer, String> {
+        let host = self.host.trim();
+        if host.is_empty() {
+            return Err("Enter a host".to_string());
+        }
+        url::Host::parse(host).map_err(|_| format!("Invalid host \"{host}\""))?;
+        let port = match self.port.trim() {
+            "" => get_game_definition(game).default_server_port,
+            port => port.parse().ok().filter(|port| *port != 0).ok_or_else(|| format!("Invalid port \"{port}\""))?,
+        };
+        let name = self.name.trim();
+        let region = self.region.trim();
+
+        Ok(RemoteServer {
+            game: game.clone(),
+            name: if name.is_empty() {format!("{host}:{port}")} else {name.to_string()},
+            host: host.to_string(),
+            port,
+            region: (!region.is_empty()).then(|| region.to_string()),
+            protocol: self.protocol,
+        })
+    }
+}
+
+fn probe_tcp(addr: &SocketAddr) -> ServerStatus {
+    let start = Instant::now();
+    match TcpStream::connect_timeout(addr, CHECK_TIMEOUT) {
+        Ok(_) => ServerStatus::Reachable(start.elapsed()),
+        Err(err) => ServerStatus::Unreachable(err.to_string()),
+    }
+}
+
+fn probe_udp(addr: &SocketAddr) -> io::Result<ServerStatus> {
+    let socket = UdpSocket::bind(if addr.is_ipv4() {"0.0.0.0:0"} else {"[::]:0"})?;
+    // connecting makes a closed port show up as a refused receive
+    socket.connect(addr)?;
+    socket.set_read_timeout(Some(CHECK_TIMEOUT))?;
+
+    let start = Instant::now();
+    socket.send(&[0; 4])?;
+    let mut buf = [0; 512];
+    Ok(match socket.recv(&mut buf) {
+        Ok(_) => ServerStatus::Reachable(start.elapsed()),
+        Err(err) if matches!(err.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => ServerStatus::NoReply,
+        Err(err) => ServerStatus::Unreachable(err.to_string()),
+    })
+}
+
+fn probe(host: &str, port: u16, protocol: ServerProtocol) -> ServerStatus {
+    let addr = match (host, port).to_socket_addrs().map(|mut addrs| addrs.next()) {
+        Ok(Some(addr)) => addr,
+        Ok(None) => return ServerStatus::Unreachable("no address".to_string()),
+        Err(err) => return ServerStatus::Unreachable(err.to_string()),
+    };
+    match protocol {
+        ServerProtocol::Tcp => probe_tcp(&addr),
+        ServerProtocol::Udp => probe_udp(&addr).unwrap_or_else(|err| ServerStatus::Unreachable(err.to_string())),
+    }
+}
+
+/// Checks whether a server answers and how long it takes, on a thread so resolving and connecting don't block the ui.
+pub async fn check_server(server: RemoteServer) -> ServerStatus {
+    let (sender, receiver) = oneshot::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(probe(&server.host, server.port, server.protocol));
+    });
+    receiver.await.unwrap_or_else(|_| ServerStatus::Unreachable("check failed".to_string()))
+}
+
+fn get_status_dot<'a>(color: Color) -> Element<'a, Message> {
+    container(Space::new(Length::Fixed(10.0), Length::Fixed(10.0)))
+        .style(move |_| container::Style {
+            background: Some(color.into()),
+            border: border::rounded(5),
+            ..container::Style::default()
+        })
+        .into()
+}
+
+fn get_status<'a>(status: Option<&ServerStatus>) -> (Element<'a, Message>, String) {
+    match status {
+        None => (get_status_dot(Color::from_rgb8(120, 120, 120)), "Checking...".to_string()),
+        Some(ServerStatus::Reachable(latency)) => (get_status_dot(Color::from_rgb8(80, 200, 120)), format!("{} ms", latency.as_millis())),
+        Some(ServerStatus::NoReply) => (get_status_dot(Color::from_rgb8(230, 180, 60)), "No reply".to_string()),
+        Some(ServerStatus::Unreachable(err)) => (get_status_dot(Color::from_rgb8(220, 70, 70)), format!("Unreachable: {err}")),
+    }
+}
+
+fn get_server_row<'a>(
+    status: (Element<'a, Message>, String),
+    name: String,
+    details: String,
+    selected: bool,
+    choice: ServerChoice,
+    actions: Vec<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    let (dot, status_text) = status;
+    let mut server_row = row![
+        dot,
+        column![
+            text(name).size(17),
+            text(details).size(13).color(Color::from_rgba8(255, 255, 255, 0.6)),
+        ].width(Length::Fill),
+        text(status_text).size(14).width(Length::Fixed(200.0)),
+        get_small_button(if selected {"Selected"} else {"Use"}, (!selected).then_some(Message::SelectServer(choice))),
+    ].spacing(15).align_y(Center);
+    for action in actions {
+        server_row = server_row.push(action);
+    }
+
+    container(server_row)
+        .padding(12)
+        .width(Length::Fill)
+        .style(move |_| container::Style {
+            background: Some(Color::from_rgba8(255, 255, 255, if selected {0.12} else {0.05}).into()),
+            border: border::rounded(6),
+            ..container::Style::default()
+        })
+        .into()
+}
+
+/// The servers of one game: its local server, if installed, with its supervisor state, then the remote ones with their last check.
+/// `local_running` is `None` when no local server is installed.
+pub fn get_server_list_view<'a>(
+    game: &PossibleGames,
+    local_running: Option<bool>,
+    servers: Vec<&'a RemoteServer>,
+    statuses: &HashMap<String, ServerStatus>,
+    selected: Option<&ServerChoice>,
+    form: &'a ServerForm,
+) -> Element<'a, Message> {
+    let definition = get_game_definition(game);
+    let mut rows = Column::new().spacing(8);
+
+    if let Some(running) = local_running {
+        let status = if running {
+            (get_status_dot(Color::from_rgb8(80, 200, 120)), "Running".to_string())
+        } else {
+            (get_status_dot(Color::from_rgb8(120, 120, 120)), "Stopped".to_string())
+        };
+        let toggle = if running {
+            get_small_button("Stop", Some(Message::StopServer(game.clone())))
+        } else {
+            get_small_button("Start", Some(Message::StartServer(game.clone())))
+        };
+        rows = rows.push(get_server_row(
+            status,
+            "Local server".to_string(),
+            format!("127.0.0.1:{}", definition.default_server_port),
+            selected == Some(&ServerChoice::Local),
+            ServerChoice::Local,
+            vec![
+                get_small_button("Logs", Some(Message::OpenServerLog(game.clone()))),
+                get_small_button("Config", Some(Message::OpenServerConfig(game.clone()))),
+                toggle,
+            ],
+        ));
+    }
+
+    for server in servers {
+        let address = server.address();
+        let protocol = match server.protocol {
+            ServerProtocol::Tcp => "TCP",
+            ServerProtocol::Udp => "UDP",
+        };
+        let details = match &server.region {
+            Some(region) => format!("{address} ({protocol}) - {region}"),
+            None => format!("{address} ({protocol})"),
+        };
+        let choice = ServerChoice::Remote(address.clone());
+        rows = rows.push(get_server_row(
+            get_status(statuses.get(&address)),
+            server.name.clone(),
+            details,
+            selected == Some(&choice),
+            choice,
+            vec![get_small_button("Remove", Some(Message::RemoveServer(address)))],
+        ));
+    }
+
+    let form_row = row![
+        text_input("Name", &form.name).on_input(Message::ServerFormName).padding(8).width(Length::FillPortion(3)),
+        text_input("Host", &form.host).on_input(Message::ServerFormHost).padding(8).width(Length::FillPortion(3)),
+        text_input(&definition.default_server_port.to_string(), &form.port).on_input(Message::ServerFormPort).padding(8).width(Length::FillPortion(1)),
+        text_input("Region", &form.region).on_input(Message::ServerFormRegion).padding(8).width(Length::FillPortion(2)),
+        mouse_area(text(match form.protocol {ServerProtocol::Tcp => "TCP", ServerProtocol::Udp => "UDP"}).size(15))
+            .on_release(Message::ServerFormToggleProtocol)
+            .interaction(iced::mouse::Interaction::Pointer),
+        get_small_button("Add", Some(Message::AddServer)),
+    ].spacing(10).align_y(Center);
+
+    let header = row![
+        mouse_area(get_icon(Icon::Back, 25.0))
+            .on_release(Message::CloseServerList),
+        text(format!("Servers - {}", definition.display_name)).size(25).font(Font::with_name("Montserrat-SemiBold")),
+    ].spacing(20).align_y(Center);
+
+    get_panel(column![
+        header,
+        scrollable(rows).height(Length::Fill),
+        form_row,
+    ].spacing(20))
+}
@@ -0,0 +1,418 @@
+use std::{
+    collections::VecDeque, fs::{self, File}, io::{BufRead, BufReader, Read, Write}, path::PathBuf, sync::{Arc, LazyLock, Mutex}
+};
+
+use iced::{
+    widget::{column, container, mouse_area, pick_list, rich_text, row, scrollable, span, text, text_input, Column},
+    Alignment::Center, Color, Element, Font, Length
+};
+use regex::{Regex, RegexBuilder};
+
+use crate::{Message, PossibleGames};
+
+use super::{game_registry::get_game_definition, icons::{get_icon, Icon}, paths::get_data_dir, supervisor::ProcessKind, widgets::{get_panel, get_small_button}};
+
+/// Lines kept in memory per server, older ones are only in the log file.
+const LOG_CAPACITY: usize = 5000;
+/// Lines rendered at once, rendering thousands of rich text lines every frame is too slow.
+const VISIBLE_LINES: usize = 500;
+
+static LEVEL_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(fatal|critical|error|err|warning|warn|info|debug|trace)\b").unwrap()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            LogLevel::Trace => "Trace",
+            LogLevel::Debug => "Debug",
+            LogLevel::Info => "Info",
+            LogLevel::Warn => "Warn",
+            LogLevel::Error => "Error",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+/// A run of text in one ANSI colour, `None` is the default colour.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogSpan {
+    pub text: String,
+    pub color: Option<Color>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub stream: LogStream,
+    pub level: Option<LogLevel>,
+    /// The line without escape codes, what filters and searches match against.
+    pub text: String,
+    pub spans: Vec<LogSpan>,
+}
+
+fn get_ansi_color(code: u32) -> Option<Color> {
+    let (r, g, b) = match code {
+        30 | 90 => (128, 128, 128),
+        31 => (222, 56, 43),
+        32 => (57, 181, 74),
+        33 => (255, 199, 6),
+        34 => (0, 111, 184),
+        35 => (118, 38, 113),
+        36 => (44, 181, 233),
+        37 => (204, 204, 204),
+        91 => (255, 85, 85),
+        92 => (85, 255, 85),
+        93 => (255, 255, 85),
+        94 => (85, 85, 255),
+        95 => (255, 85, 255),
+        96 => (85, 255, 255),
+        97 => (255, 255, 255),
+        _ => return None,
+    };
+    Some(Color::from_rgb8(r, g, b))
+}
+
+/// Applies the parameters of an SGR sequence (`ESC [ ... m`) to the current colour.
+pub(crate) fn apply_sgr(params: &str, color: &mut Option<Color>) {
+    let mut codes = params.split(';').map(|code| code.parse::<u32>().unwrap_or(0));
+    while let Some(code) = codes.next() {
+        match code {
+            0 | 39 => *color = None,
+            30..=37 | 90..=97 => *color = get_ansi_color(code),
+            38 => match codes.next() {
+                Some(5) => {
+                    // only the 16 basic entries of the 256 colour palette map onto named colours
+                    *color = codes.next().and_then(|index| match index {
+                        0..=7 => get_ansi_color(index + 30),
+                        8..=15 => get_ansi_color(index + 82),
+                        _ => None,
+                    });
+                },
+                Some(2) => {
+                    let rgb: Vec<u8> = codes.by_ref().take(3).map(|value| value.min(255) as u8).collect();
+                    if let [r, g, b] = rgb[..] {
+                        *color = Some(Color::from_rgb8(r, g, b));
+                    }
+                },
+                _ => {},
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Splits a line into coloured spans, dropping every escape sequence.
+pub fn parse_ansi(line: &str) -> Vec<LogSpan> {
+    let mut spans = Vec::new();
+    let mut color = None;
+    let mut current = String::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            current.push(c);
+            continue;
+        }
+        if chars.peek() != Some(&'[') {
+            continue;
+        }
+        chars.next();
+
+        let mut params = String::new();
+        let mut command = None;
+        for c in chars.by_ref() {
+            if c.is_ascii_alphabetic() {
+                command = Some(c);
+                break;
+            }
+            params.push(c);
+        }
+        if command == Some('m') {
+            let mut new_color = color;
+            apply_sgr(&params, &mut new_color);
+            if new_color != color && !current.is_empty() {
+                spans.push(LogSpan { text: std::mem::take(&mut current), color });
+            }
+            color = new_color;
+        }
+    }
+    if !current.is_empty() {
+        spans.push(LogSpan { text: current, color });
+    }
+    spans
+}
+
+pub fn detect_level(line: &str) -> Option<LogLevel> {
+    let level = LEVEL_PATTERN.find(line)?.as_str().to_lowercase();
+    Some(match level.as_str() {
+        "fatal" | "critical" | "error" | "err" => LogLevel::Error,
+        "warning" | "warn" => LogLevel::Warn,
+        "info" => LogLevel::Info,
+        "debug" => LogLevel::Debug,
+        _ => LogLevel::Trace,
+    })
+}
+
+pub fn parse_log_line(stream: LogStream, line: &str) -> LogLine {
+    let spans = parse_ansi(line);
+    let text: String = spans.iter().map(|span| span.text.as_str()).collect();
+    let level = detect_level(&text);
+    LogLine { stream, level, text, spans }
+}
+
+/// Ring buffer of a process's latest output, shared with the threads reading its pipes.
+#[derive(Debug, Default)]
+pub struct LogBuffer {
+    lines: VecDeque<LogLine>,
+    /// Lines pushed since the buffer was created, lets readers tell whether anything changed.
+    pub total: u64,
+}
+
+impl LogBuffer {
+    pub fn push(&mut self, line: LogLine) {
+        if self.lines.len() == LOG_CAPACITY {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+        self.total += 1;
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &LogLine> {
+        self.lines.iter()
+    }
+
+    /// Plain text of the last `count` lines, oldest first.
+    pub fn tail(&self, count: usize) -> Vec<String> {
+        self.lines.iter().skip(self.lines.len().saturating_sub(count)).map(|line| line.text.clone()).collect()
+    }
+}
+
+pub type SharedLog = Arc<Mutex<LogBuffer>>;
+
+/// Server logs are written to `<data dir>/logs/`, next to the launcher's own.
+pub fn get_log_dir() -> Option<PathBuf> {
+    Some(get_data_dir()?.join("logs"))
+}
+
+/// Starts a new `<game>-<client|server>.log`, keeping the previous run as `<game>-<client|server>.previous.log`.
+pub fn create_log_file(game: &PossibleGames, kind: ProcessKind) -> Option<Arc<Mutex<File>>> {
+    let dir = get_log_dir()?;
+    fs::create_dir_all(&dir).ok()?;
+
+    let name = format!("{}-{}", get_game_definition(game).slug, match kind {
+        ProcessKind::Client => "client",
+        ProcessKind::Server => "server",
+    });
+    let path = dir.join(format!("{name}.log"));
+    let _ = fs::rename(&path, dir.join(format!("{name}.previous.log")));
+    File::create(path).ok().map(|file| Arc::new(Mutex::new(file)))
+}
+
+/// Reads a pipe of a client or server until it closes, into the ring buffer and the log file.
+pub fn spawn_log_reader(pipe: impl Read + Send + 'static, stream: LogStream, log: SharedLog, file: Option<Arc<Mutex<File>>>) {
+    std::thread::spawn(move || {
+        let reader = BufReader::new(pipe);
+        // servers don't always write valid utf-8, so split on bytes and convert lossily
+        for line in reader.split(b'\n').map_while(Result::ok) {
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches('\r');
+            if let Some(file) = file.as_ref() && let Ok(mut file) = file.lock() {
+                let _ = writeln!(file, "{line}");
+            }
+            if let Ok(mut log) = log.lock() {
+                log.push(parse_log_line(stream, line));
+            }
+        }
+    });
+}
+
+/// Which lines the log view shows and whether it keeps up with new output.
+#[derive(Debug)]
+pub struct LogViewer {
+    pub game: PossibleGames,
+    pub filter: String,
+    pub search: String,
+    pub min_level: Option<LogLevel>,
+    /// New output is only copied in while following, pausing freezes the view.
+    pub follow: bool,
+    filter_pattern: Result<Option<Regex>, String>,
+    search_pattern: Result<Option<Regex>, String>,
+    lines: Vec<LogLine>,
+    seen: u64,
+}
+
+fn compile_pattern(pattern: &str) -> Result<Option<Regex>, String> {
+    if pattern.is_empty() {
+        return Ok(None);
+    }
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map(Some)
+        .map_err(|_| "Invalid regex".to_string())
+}
+
+impl LogViewer {
+    pub fn new(game: PossibleGames) -> Self {
+        LogViewer {
+            game,
+            filter: String::new(),
+            search: String::new(),
+            min_level: None,
+            follow: true,
+            filter_pattern: Ok(None),
+            search_pattern: Ok(None),
+            lines: Vec::new(),
+            seen: 0,
+        }
+    }
+
+    pub fn set_filter(&mut self, filter: String) {
+        self.filter_pattern = compile_pattern(&filter);
+        self.filter = filter;
+    }
+
+    pub fn set_search(&mut self, search: String) {
+        self.search_pattern = compile_pattern(&search);
+        self.search = search;
+    }
+
+    /// Copies the buffer's lines when following and something changed since the last refresh.
+    pub fn refresh(&mut self, log: &SharedLog) {
+        if !self.follow {
+            return;
+        }
+        if let Ok(log) = log.lock() && log.total != self.seen {
+            self.lines = log.lines().cloned().collect();
+            self.seen = log.total;
+        }
+    }
+
+    fn is_visible(&self, line: &LogLine) -> bool {
+        let level_matches = match self.min_level {
+            Some(min_level) => line.level.is_some_and(|level| level >= min_level),
+            None => true,
+        };
+        let filter_matches = match &self.filter_pattern {
+            Ok(Some(pattern)) => pattern.is_match(&line.text),
+            _ => true,
+        };
+        level_matches && filter_matches
+    }
+
+    fn is_search_match(&self, line: &LogLine) -> bool {
+        matches!(&self.search_pattern, Ok(Some(pattern)) if pattern.is_match(&line.text))
+    }
+}
+
+fn get_level_color(level: Option<LogLevel>) -> Color {
+    match level {
+        Some(LogLevel::Error) => Color::from_rgb8(240, 100, 100),
+        Some(LogLevel::Warn) => Color::from_rgb8(240, 190, 80),
+        Some(LogLevel::Debug | LogLevel::Trace) => Color::from_rgba8(255, 255, 255, 0.55),
+        Some(LogLevel::Info) | None => Color::WHITE,
+    }
+}
+
+fn get_log_line<'a>(line: &'a LogLine, highlighted: bool) -> Element<'a, Message> {
+    let default_color = match (line.stream, line.level) {
+        (LogStream::Stderr, None) => Color::from_rgb8(240, 100, 100),
+        (_, level) => get_level_color(level),
+    };
+    let spans: Vec<text::Span<'a, (), Font>> = line.spans.iter()
+        .map(|log_span| span(log_span.text.as_str()).color(log_span.color.unwrap_or(default_color)))
+        .collect();
+
+    container(rich_text(spans).size(13).font(Font::MONOSPACE))
+        .width(Length::Fill)
+        .padding([1, 6])
+        .style(move |_| container::Style {
+            background: highlighted.then(|| Color::from_rgba8(255, 220, 80, 0.2).into()),
+            ..container::Style::default()
+        })
+        .into()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LevelChoice {
+    All,
+    AtLeast(LogLevel),
+}
+
+impl std::fmt::Display for LevelChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LevelChoice::All => write!(f, "All levels"),
+            LevelChoice::AtLeast(level) => write!(f, "{level} and above"),
+        }
+    }
+}
+
+pub fn get_server_log_view(viewer: &LogViewer) -> Element<Message> {
+    let visible: Vec<&LogLine> = viewer.lines.iter().filter(|line| viewer.is_visible(line)).collect();
+    let matches = visible.iter().filter(|line| viewer.is_search_match(line)).count();
+    let skipped = visible.len().saturating_sub(VISIBLE_LINES);
+
+    let mut lines = Column::new();
+    if skipped > 0 {
+        lines = lines.push(text(format!("{skipped} earlier lines are in the log file")).size(13).color(Color::from_rgba8(255, 255, 255, 0.5)));
+    }
+    for line in &visible[skipped..] {
+        lines = lines.push(get_log_line(line, viewer.is_search_match(line)));
+    }
+    let mut log = scrollable(lines.width(Length::Fill)).height(Length::Fill);
+    if viewer.follow {
+        log = log.anchor_bottom();
+    }
+
+    let level_choices = [
+        LevelChoice::All,
+        LevelChoice::AtLeast(LogLevel::Debug),
+        LevelChoice::AtLeast(LogLevel::Info),
+        LevelChoice::AtLeast(LogLevel::Warn),
+        LevelChoice::AtLeast(LogLevel::Error),
+    ];
+    let selected_level = viewer.min_level.map_or(LevelChoice::All, LevelChoice::AtLeast);
+    let search_status = match &viewer.search_pattern {
+        Err(err) => err.clone(),
+        Ok(Some(_)) => format!("{matches} matches"),
+        Ok(None) => String::new(),
+    };
+    let filter_status = viewer.filter_pattern.as_ref().err().cloned().unwrap_or_default();
+
+    let header = row![
+        mouse_area(get_icon(Icon::Back, 25.0))
+            .on_release(Message::CloseServerLog),
+        text(format!("Server log - {}", get_game_definition(&viewer.game).display_name)).size(25).font(Font::with_name("Montserrat-SemiBold")).width(Length::Fill),
+        get_small_button(if viewer.follow {"Pause"} else {"Follow"}, Some(Message::ToggleServerLogFollow)),
+        get_small_button("Open log folder", Some(Message::OpenLogFolder)),
+    ].spacing(20).align_y(Center);
+
+    let controls = row![
+        text_input("Filter (regex)", &viewer.filter).on_input(Message::ServerLogFilterChanged).padding(8).width(Length::FillPortion(2)),
+        text(filter_status).size(13),
+        text_input("Search (regex)", &viewer.search).on_input(Message::ServerLogSearchChanged).padding(8).width(Length::FillPortion(2)),
+        text(search_status).size(13),
+        pick_list(level_choices, Some(selected_level), |choice| Message::ServerLogLevelChanged(match choice {
+            LevelChoice::All => None,
+            LevelChoice::AtLeast(level) => Some(level),
+        })).text_size(14),
+    ].spacing(10).align_y(Center);
+
+    get_panel(column![header, controls, log].spacing(15))
+}
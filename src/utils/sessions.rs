@@ -0,0 +1,200 @@
+use std::{
+    fs::{self, OpenOptions}, io::{self, BufRead, BufReader, Write}, path::PathBuf, time::Duration
+};
+
+use chrono::{DateTime, Local, Utc};
+use iced::{
+    widget::{column, mouse_area, row, scrollable, text, Column},
+    Alignment::Center, Color, Element, Font, Length
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{Message, PossibleGames};
+
+use super::{game_registry::get_game_definition, icons::{get_icon, Icon}, paths::get_data_dir, supervisor::{ProcessExit, ProcessKind}, widgets::{get_panel, get_small_button}};
+
+const RECENT_SESSIONS: usize = 20;
+
+/// One run of a client or server started by the launcher.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub game: PossibleGames,
+    pub kind: ProcessKind,
+    pub started: DateTime<Utc>,
+    pub ended: DateTime<Utc>,
+    /// `None` when the process was killed by a signal.
+    pub exit_code: Option<i32>,
+    pub profile: Option<String>,
+}
+
+impl Session {
+    pub fn from_exit(exit: &ProcessExit, profile: Option<String>) -> Self {
+        Session {
+            game: exit.game.clone(),
+            kind: exit.kind,
+            started: exit.started.into(),
+            ended: exit.ended.into(),
+            exit_code: exit.status.and_then(|status| status.code()),
+            profile,
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        (self.ended - self.started).to_std().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// Sessions are appended to `<data dir>/sessions.jsonl`, one json object per line so a crash can't corrupt earlier ones.
+fn get_sessions_path() -> Option<PathBuf> {
+    Some(get_data_dir()?.join("sessions.jsonl"))
+}
+
+pub fn load_sessions() -> Vec<Session> {
+    let Some(file) = get_sessions_path().and_then(|path| fs::File::open(path).ok()) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+pub fn record_session(session: &Session) -> io::Result<()> {
+    let path = get_sessions_path().ok_or(io::ErrorKind::NotFound)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let line = serde_json::to_string(session).map_err(io::Error::other)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+pub(crate) fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn get_kind_name(kind: ProcessKind) -> &'static str {
+    match kind {
+        ProcessKind::Client => "client",
+        ProcessKind::Server => "server",
+    }
+}
+
+/// One row per session under a header, durations in whole seconds.
+pub(crate) fn get_sessions_csv(sessions: &[Session]) -> String {
+    let mut csv = String::from("game,kind,started,ended,duration_seconds,exit_code,profile\n");
+    for session in sessions {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            get_game_definition(&session.game).slug,
+            get_kind_name(session.kind),
+            session.started.to_rfc3339(),
+            session.ended.to_rfc3339(),
+            session.duration().as_secs(),
+            session.exit_code.map(|code| code.to_string()).unwrap_or_default(),
+            escape_csv(session.profile.as_deref().unwrap_or_default()),
+        ));
+    }
+    csv
+}
+
+/// Writes the sessions to `<data dir>/exports/sessions-<time>.<csv|json>`.
+pub fn export_sessions(sessions: &[Session], format: ExportFormat) -> io::Result<PathBuf> {
+    let dir = get_data_dir().ok_or(io::ErrorKind::NotFound)?.join("exports");
+    fs::create_dir_all(&dir)?;
+
+    let timestamp = Local::now().format("%Y%m%d-%H%M%S");
+    let (path, contents) = match format {
+        ExportFormat::Json => (
+            dir.join(format!("sessions-{timestamp}.json")),
+            serde_json::to_string_pretty(sessions).map_err(io::Error::other)?,
+        ),
+        ExportFormat::Csv => (dir.join(format!("sessions-{timestamp}.csv")), get_sessions_csv(sessions)),
+    };
+    fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// Formats a duration as `3h 25m`, or `12m` under an hour.
+pub fn format_playtime(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    match minutes / 60 {
+        0 => format!("{minutes}m"),
+        hours => format!("{hours}h {}m", minutes % 60),
+    }
+}
+
+fn format_time(time: &DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string()
+}
+
+fn get_stat<'a>(label: &'a str, value: String) -> Element<'a, Message> {
+    column![
+        text(label).size(14).color(Color::from_rgba8(255, 255, 255, 0.6)),
+        text(value).size(22),
+    ].spacing(4).width(Length::Fill).into()
+}
+
+/// Total playtime, last played and recent sessions of a game, clients and servers alike.
+pub fn get_sessions_view<'a>(game: &PossibleGames, sessions: &'a [Session]) -> Element<'a, Message> {
+    let game_sessions: Vec<&Session> = sessions.iter().filter(|session| session.game == *game).collect();
+    let total = |kind: ProcessKind| game_sessions.iter()
+        .filter(|session| session.kind == kind)
+        .map(|session| session.duration())
+        .sum::<Duration>();
+    let last_played = game_sessions.iter()
+        .filter(|session| session.kind == ProcessKind::Client)
+        .map(|session| session.ended)
+        .max();
+
+    let header = row![
+        mouse_area(get_icon(Icon::Back, 25.0))
+            .on_release(Message::CloseSessions),
+        text(format!("Playtime - {}", get_game_definition(game).display_name)).size(25).font(Font::with_name("Montserrat-SemiBold")).width(Length::Fill),
+        get_small_button("Export CSV", Some(Message::ExportSessions(ExportFormat::Csv))),
+        get_small_button("Export JSON", Some(Message::ExportSessions(ExportFormat::Json))),
+    ].spacing(20).align_y(Center);
+
+    let stats = row![
+        get_stat("Total playtime", format_playtime(total(ProcessKind::Client))),
+        get_stat("Server uptime", format_playtime(total(ProcessKind::Server))),
+        get_stat("Last played", last_played.as_ref().map_or("Never".to_string(), format_time)),
+    ].spacing(20);
+
+    let mut recent = Column::new().spacing(6);
+    if game_sessions.is_empty() {
+        recent = recent.push(text("No sessions yet, they are recorded for games and servers started from the launcher").size(15));
+    }
+    for session in game_sessions.iter().rev().take(RECENT_SESSIONS) {
+        let exit = match session.exit_code {
+            Some(0) => "Exited normally".to_string(),
+            Some(code) => format!("Exited with {code}"),
+            None => "Killed".to_string(),
+        };
+        recent = recent.push(row![
+            text(format_time(&session.started)).size(14).width(Length::Fixed(150.0)),
+            text(match session.kind {ProcessKind::Client => "Game", ProcessKind::Server => "Server"}).size(14).width(Length::Fixed(70.0)),
+            text(format_playtime(session.duration())).size(14).width(Length::Fixed(90.0)),
+            text(session.profile.clone().unwrap_or_else(|| "Default".to_string())).size(14).width(Length::Fill),
+            text(exit).size(14).width(Length::Fixed(140.0)),
+        ].spacing(10));
+    }
+
+    get_panel(column![
+        header,
+        stats,
+        text("Recent sessions").size(18),
+        scrollable(recent).height(Length::Fill),
+    ].spacing(20))
+}
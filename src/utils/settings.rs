@@ -0,0 +1,155 @@
+use std::path::{Path, PathBuf};
+
+use iced::{
+    widget::{checkbox, column, mouse_area, pick_list, row, text, text_input},
+    Alignment::Center, Color, Element, Font, Length
+};
+
+use crate::{Message, PossibleGames};
+
+use super::{game_registry::get_game_definition, hooks::{GameHooks, Hook, HookStage}, icons::{get_icon, Icon}, language::{get_language_choices, normalize_language, LanguageChoice}, supervisor::ProcessKind, widgets::get_panel};
+
+#[derive(Debug, Default)]
+pub struct HookFields {
+    pub command: String,
+    pub timeout: String,
+}
+
+impl HookFields {
+    fn new(hook: Option<&Hook>) -> Self {
+        HookFields {
+            command: hook.map(|hook| hook.command.clone()).unwrap_or_default(),
+            timeout: hook.map(|hook| hook.timeout_secs.to_string()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Text of the settings being edited for one game, only valid values are saved.
+#[derive(Debug)]
+pub struct SettingsPanel {
+    pub game: PossibleGames,
+    pub game_install: String,
+    pub server_install: String,
+    pub pre_launch: HookFields,
+    pub post_exit: HookFields,
+    pub abort_on_failure: bool,
+    pub error: Option<String>,
+}
+
+impl SettingsPanel {
+    pub fn new(game: PossibleGames, game_install: Option<&PathBuf>, server_install: Option<&PathBuf>, hooks: Option<&GameHooks>) -> Self {
+        let display = |path: Option<&PathBuf>| path.map(|path| path.display().to_string()).unwrap_or_default();
+        let pre_launch = hooks.and_then(|hooks| hooks.pre_launch.as_ref());
+        SettingsPanel {
+            game,
+            game_install: display(game_install),
+            server_install: display(server_install),
+            pre_launch: HookFields::new(pre_launch),
+            post_exit: HookFields::new(hooks.and_then(|hooks| hooks.post_exit.as_ref())),
+            abort_on_failure: pre_launch.is_none_or(|hook| hook.abort_on_failure),
+            error: None,
+        }
+    }
+
+    pub fn hook_fields(&mut self, stage: HookStage) -> &mut HookFields {
+        match stage {
+            HookStage::PreLaunch => &mut self.pre_launch,
+            HookStage::PostExit => &mut self.post_exit,
+        }
+    }
+
+    /// The game's hook for `stage` as entered, `None` without a command. An empty timeout uses the default.
+    pub fn parse_hook(&self, stage: HookStage) -> Result<Option<Hook>, String> {
+        let fields = match stage {
+            HookStage::PreLaunch => &self.pre_launch,
+            HookStage::PostExit => &self.post_exit,
+        };
+        let command = fields.command.trim();
+        if command.is_empty() {
+            return Ok(None);
+        }
+        let mut hook = Hook::new(command.to_string());
+        let timeout = fields.timeout.trim();
+        if !timeout.is_empty() {
+            hook.timeout_secs = timeout.parse().ok().filter(|secs| *secs > 0)
+                .ok_or_else(|| format!("{timeout} is not a number of seconds"))?;
+        }
+        hook.abort_on_failure = self.abort_on_failure;
+        Ok(Some(hook))
+    }
+}
+
+/// Checks that `path` is a folder holding the client or server executable, an empty path clears the setting.
+pub fn parse_install_dir(game: &PossibleGames, kind: ProcessKind, path: &str) -> Result<Option<PathBuf>, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Ok(None);
+    }
+    let dir = Path::new(path);
+    if !dir.is_dir() {
+        return Err(format!("{path} is not a folder"));
+    }
+    let definition = get_game_definition(game);
+    let executable = match kind {
+        ProcessKind::Client => definition.client_executable.to_string(),
+        ProcessKind::Server => format!("{}{}", definition.server_executable, std::env::consts::EXE_SUFFIX),
+    };
+    if !dir.join(&executable).is_file() {
+        return Err(format!("{executable} is not in {path}"));
+    }
+    Ok(Some(dir.to_path_buf()))
+}
+
+fn get_hook_fields<'a>(label: &'a str, stage: HookStage, fields: &'a HookFields) -> Element<'a, Message> {
+    column![
+        text(label).size(15),
+        row![
+            text_input("Command, run with RR_GAME, RR_INSTALL_DIR, RR_PROFILE and RR_SERVER_* set", &fields.command)
+                .on_input(move |command| Message::HookCommandChanged(stage, command))
+                .padding(8),
+            text_input("Timeout (s)", &fields.timeout)
+                .on_input(move |timeout| Message::HookTimeoutChanged(stage, timeout))
+                .padding(8)
+                .width(Length::Fixed(120.0)),
+        ].spacing(10),
+    ].spacing(5).into()
+}
+
+/// `language` is the launcher-wide setting, the rest belongs to the panel's game.
+pub fn get_settings_view(panel: &SettingsPanel, language: Option<&str>) -> Element<Message> {
+    let definition = get_game_definition(&panel.game);
+    let header = row![
+        mouse_area(get_icon(Icon::Back, 25.0))
+            .on_release(Message::CloseSettings),
+        text(format!("Settings - {}", definition.display_name)).size(25).font(Font::with_name("Montserrat-SemiBold")),
+    ].spacing(20).align_y(Center);
+
+    let language = language.and_then(normalize_language).map_or(LanguageChoice::System, LanguageChoice::Language);
+    let mut content = column![
+        header,
+        column![
+            text("Language, for trailer audio and subtitles").size(15),
+            pick_list(get_language_choices(), Some(language), Message::LanguageSelected).padding(8),
+        ].spacing(5),
+        column![
+            text("Game folder").size(15),
+            text_input(&format!("Folder containing {}", definition.client_executable), &panel.game_install)
+                .on_input(Message::GameInstallChanged)
+                .padding(8),
+        ].spacing(5),
+        column![
+            text("Server folder").size(15),
+            text_input(&format!("Folder containing {}", definition.server_executable), &panel.server_install)
+                .on_input(Message::ServerInstallChanged)
+                .padding(8),
+        ].spacing(5),
+        get_hook_fields("Pre-launch hook", HookStage::PreLaunch, &panel.pre_launch),
+        checkbox("Abort the launch when the pre-launch hook fails", panel.abort_on_failure).on_toggle(Message::HookAbortToggled),
+        get_hook_fields("Post-exit hook", HookStage::PostExit, &panel.post_exit),
+    ].spacing(20).max_width(700);
+    if let Some(err) = &panel.error {
+        content = content.push(text(err).size(14).color(Color::from_rgb8(240, 100, 100)));
+    }
+
+    get_panel(content)
+}
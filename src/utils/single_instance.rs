@@ -0,0 +1,82 @@
+use std::{io::{BufRead, BufReader, Write}, sync::{mpsc, Mutex, OnceLock}};
+
+use interprocess::local_socket::{prelude::*, GenericNamespaced, ListenerOptions, Stream};
+
+use crate::PossibleGames;
+
+use super::game_registry::get_game_by_slug;
+
+// a named pipe on windows, an abstract socket on linux and a socket file in /tmp elsewhere
+const SOCKET_NAME: &str = "reversed-rooms-launcher.sock";
+
+static FORWARDED_COMMANDS: OnceLock<Mutex<mpsc::Receiver<Vec<String>>>> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LauncherCommand {
+    Launch(PossibleGames),
+    DeepLink(url::Url),
+}
+
+/// Parses the arguments the launcher was started with: nothing, `launch <game>` or an `rrlauncher://` link.
+pub fn parse_launcher_command(args: &[String]) -> Result<Option<LauncherCommand>, String> {
+    match args {
+        [] => Ok(None),
+        [command, slug] if command == "launch" => get_game_by_slug(slug)
+            .map(|game| Some(LauncherCommand::Launch(game)))
+            .ok_or_else(|| format!("Unknown game \"{slug}\"")),
+        [link] if link.starts_with("rrlauncher:") => url::Url::parse(link)
+            .map(|url| Some(LauncherCommand::DeepLink(url)))
+            .map_err(|err| format!("Invalid link: {err}")),
+        _ => Err(format!("Unknown command \"{}\"", args.join(" "))),
+    }
+}
+
+fn forward_args(mut stream: Stream, args: &[String]) -> std::io::Result<()> {
+    let line = serde_json::to_string(args)?;
+    writeln!(stream, "{line}")?;
+    stream.flush()
+}
+
+/// Makes this process the only running launcher. Returns `false` if another one is already running,
+/// in which case `args` were handed to it and this process should exit.
+pub fn claim_single_instance(args: &[String]) -> bool {
+    let Ok(name) = SOCKET_NAME.to_ns_name::<GenericNamespaced>() else {
+        return true;
+    };
+
+    if let Ok(stream) = Stream::connect(name.clone()) {
+        if forward_args(stream, args).is_ok() {
+            return false;
+        }
+    }
+
+    // nobody answered, so a leftover socket file belongs to a launcher that crashed
+    let Ok(listener) = ListenerOptions::new().name(name).try_overwrite(true).create_sync() else {
+        return true;
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let _ = FORWARDED_COMMANDS.set(Mutex::new(receiver));
+    std::thread::spawn(move || {
+        for stream in listener.incoming().filter_map(Result::ok) {
+            let mut line = String::new();
+            if BufReader::new(stream).read_line(&mut line).is_err() {
+                continue;
+            }
+            if let Ok(args) = serde_json::from_str::<Vec<String>>(&line) {
+                if sender.send(args).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    true
+}
+
+/// Drains the arguments forwarded by other invocations since the last call.
+pub fn get_forwarded_commands() -> Vec<Vec<String>> {
+    FORWARDED_COMMANDS
+        .get()
+        .and_then(|receiver| receiver.lock().ok().map(|receiver| receiver.try_iter().collect()))
+        .unwrap_or_default()
+}
@@ -0,0 +1,263 @@
+use std::{collections::HashMap, fmt, path::Path, process::{Child, Command, ExitStatus, Stdio}, sync::Arc, thread, time::{Duration, Instant, SystemTime}};
+
+use serde::{Deserialize, Serialize};
+
+use crate::PossibleGames;
+
+use super::{game_registry::get_game_definition, server_log::{create_log_file, spawn_log_reader, LogStream, SharedLog}};
+
+// how long a process gets to shut down on its own before it is killed
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProcessKind {
+    Client,
+    Server,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessStatus {
+    Running,
+    // asked to exit, waiting to be reaped by the next poll
+    Stopping,
+}
+
+#[derive(Debug)]
+struct SupervisedProcess {
+    child: Child,
+    started: SystemTime,
+    status: ProcessStatus,
+    // killed by the next poll after this if it is still running
+    kill_at: Option<Instant>,
+    // started by the poll that reaps this process
+    restart: Option<Command>,
+}
+
+/// Asks a process to exit: SIGTERM on unix, CTRL_BREAK on windows, where it was started in its own process group.
+#[cfg(unix)]
+fn request_exit(child: &Child) -> bool {
+    let Ok(pid) = i32::try_from(child.id()) else {
+        return false;
+    };
+    // SAFETY: kill only sends a signal, the pid belongs to a child that was not reaped yet
+    unsafe { libc::kill(pid, libc::SIGTERM) == 0 }
+}
+
+#[cfg(windows)]
+fn request_exit(child: &Child) -> bool {
+    use windows_sys::Win32::System::Console::{GenerateConsoleCtrlEvent, CTRL_BREAK_EVENT};
+    // SAFETY: the child is the root of its own process group, so only it and its children get the event
+    unsafe { GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, child.id()) != 0 }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn request_exit(_child: &Child) -> bool {
+    false
+}
+
+/// Waits for a process that was asked to exit, killing it if it is still running after [`STOP_TIMEOUT`].
+/// `asked` is whether the request went through, without one it is killed right away.
+fn wait_or_kill(child: &mut Child, asked: bool) {
+    if asked {
+        let deadline = Instant::now() + STOP_TIMEOUT;
+        while Instant::now() < deadline {
+            if !matches!(child.try_wait(), Ok(None)) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        tracing::warn!("process {} did not exit in time, killing it", child.id());
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// A supervised process that ended, as returned by [`Supervisor::poll`].
+#[derive(Debug, Clone)]
+pub struct ProcessExit {
+    pub game: PossibleGames,
+    pub kind: ProcessKind,
+    pub started: SystemTime,
+    pub ended: SystemTime,
+    pub status: Option<ExitStatus>,
+    /// Whether the launcher asked it to stop, which ends it through a signal rather than a clean exit.
+    pub stopped: bool,
+}
+
+impl ProcessExit {
+    /// Exited with a non-zero code or by a signal without the launcher stopping it.
+    pub fn is_crash(&self) -> bool {
+        !self.stopped && self.status.is_some_and(|status| !status.success())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SupervisorError {
+    NotInstalled(PossibleGames, ProcessKind),
+    AlreadyRunning(PossibleGames, ProcessKind),
+    Spawn(String),
+}
+
+impl fmt::Display for SupervisorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SupervisorError::NotInstalled(game, ProcessKind::Client) => write!(f, "{} is not installed", get_game_definition(game).display_name),
+            SupervisorError::NotInstalled(game, ProcessKind::Server) => write!(f, "no server installed for {}", get_game_definition(game).display_name),
+            SupervisorError::AlreadyRunning(game, ProcessKind::Client) => write!(f, "{} is already running", get_game_definition(game).display_name),
+            SupervisorError::AlreadyRunning(game, ProcessKind::Server) => write!(f, "the {} server is already running", get_game_definition(game).display_name),
+            SupervisorError::Spawn(err) => write!(f, "failed to start: {err}"),
+        }
+    }
+}
+
+/// Owns the game clients and servers started by the launcher, the window and the tray both read their state from here.
+#[derive(Debug, Default)]
+pub struct Supervisor {
+    processes: HashMap<(PossibleGames, ProcessKind), SupervisedProcess>,
+    // kept across restarts so the log view doesn't lose the previous run
+    logs: HashMap<(PossibleGames, ProcessKind), SharedLog>,
+}
+
+impl Supervisor {
+    pub fn spawn(&mut self, game: &PossibleGames, kind: ProcessKind, mut command: Command) -> Result<(), SupervisorError> {
+        if self.processes.contains_key(&(game.clone(), kind)) {
+            return Err(SupervisorError::AlreadyRunning(game.clone(), kind));
+        }
+
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            // lets stop send CTRL_BREAK to this process alone
+            command.creation_flags(windows_sys::Win32::System::Threading::CREATE_NEW_PROCESS_GROUP);
+        }
+        let mut child = command.spawn().map_err(|err| SupervisorError::Spawn(err.to_string()))?;
+        let log = self.logs.entry((game.clone(), kind)).or_default().clone();
+        let file = create_log_file(game, kind);
+        if let Some(stdout) = child.stdout.take() {
+            spawn_log_reader(stdout, LogStream::Stdout, Arc::clone(&log), file.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            spawn_log_reader(stderr, LogStream::Stderr, log, file);
+        }
+        self.processes.insert((game.clone(), kind), SupervisedProcess { child, started: SystemTime::now(), status: ProcessStatus::Running, kill_at: None, restart: None });
+        Ok(())
+    }
+
+    /// Asks a process to exit, [`Supervisor::poll`] kills it if it is still running after [`STOP_TIMEOUT`].
+    pub fn stop(&mut self, game: &PossibleGames, kind: ProcessKind) {
+        let Some(process) = self.processes.get_mut(&(game.clone(), kind)) else {
+            return;
+        };
+        // stopping wins over a pending restart
+        process.restart = None;
+        if process.status == ProcessStatus::Running {
+            let stopping = if request_exit(&process.child) {
+                process.kill_at = Some(Instant::now() + STOP_TIMEOUT);
+                true
+            } else {
+                process.child.kill().is_ok()
+            };
+            if stopping {
+                process.status = ProcessStatus::Stopping;
+            }
+        }
+    }
+
+    /// Stops a process and starts it again with `command` once [`Supervisor::poll`] reaped the old one,
+    /// so it has let go of its ports. The old process's exit is not reported.
+    pub fn restart(&mut self, game: &PossibleGames, kind: ProcessKind, command: Command) -> Result<(), SupervisorError> {
+        if !self.processes.contains_key(&(game.clone(), kind)) {
+            return self.spawn(game, kind, command);
+        }
+        self.stop(game, kind);
+        if let Some(process) = self.processes.get_mut(&(game.clone(), kind)) {
+            process.restart = Some(command);
+        }
+        Ok(())
+    }
+
+    /// Stops every server before the launcher exits, clients keep running on their own.
+    pub fn stop_servers(&mut self) {
+        // ask all of them first so they shut down in parallel, a second request can mean a forced shutdown to some
+        let asked: HashMap<_, _> = self.processes.iter()
+            .filter(|((_, kind), _)| *kind == ProcessKind::Server)
+            .map(|(key, process)| {
+                // one that is already stopping was asked before
+                let asked = process.status == ProcessStatus::Stopping || request_exit(&process.child);
+                (key.clone(), asked)
+            })
+            .collect();
+        self.processes.retain(|key, process| {
+            let Some(asked) = asked.get(key) else {
+                return true;
+            };
+            wait_or_kill(&mut process.child, *asked);
+            false
+        });
+    }
+
+    /// Captured output of a game's client or server, once it has been started.
+    pub fn output(&self, game: &PossibleGames, kind: ProcessKind) -> Option<SharedLog> {
+        self.logs.get(&(game.clone(), kind)).cloned()
+    }
+
+    pub fn status(&self, game: &PossibleGames, kind: ProcessKind) -> Option<ProcessStatus> {
+        self.processes.get(&(game.clone(), kind)).map(|process| process.status)
+    }
+
+    pub fn is_running(&self, game: &PossibleGames, kind: ProcessKind) -> bool {
+        self.status(game, kind).is_some()
+    }
+
+    /// Reaps every process that ended since the last poll and starts the ones waiting to restart,
+    /// returning the exits and the restarts that failed.
+    pub fn poll(&mut self) -> (Vec<ProcessExit>, Vec<SupervisorError>) {
+        let mut exits = Vec::new();
+        let mut restarts = Vec::new();
+        self.processes.retain(|(game, kind), process| {
+            match process.child.try_wait() {
+                Ok(None) => {
+                    if process.kill_at.is_some_and(|kill_at| Instant::now() >= kill_at) {
+                        tracing::warn!("{} {kind:?} did not exit in time, killing it", get_game_definition(game).display_name);
+                        let _ = process.child.kill();
+                        process.kill_at = None;
+                    }
+                    true
+                },
+                _ if process.restart.is_some() => {
+                    restarts.extend(process.restart.take().map(|command| (game.clone(), *kind, command)));
+                    false
+                },
+                result => {
+                    exits.push(ProcessExit {
+                        game: game.clone(),
+                        kind: *kind,
+                        started: process.started,
+                        ended: SystemTime::now(),
+                        status: result.ok().flatten(),
+                        stopped: process.status == ProcessStatus::Stopping,
+                    });
+                    false
+                }
+            }
+        });
+        let errors = restarts.into_iter()
+            .filter_map(|(game, kind, command)| self.spawn(&game, kind, command).err())
+            .collect();
+        (exits, errors)
+    }
+}
+
+pub fn get_client_command(game: &PossibleGames, install_dir: &Path) -> Command {
+    let mut command = Command::new(install_dir.join(get_game_definition(game).client_executable));
+    command.current_dir(install_dir);
+    command
+}
+
+pub fn get_server_command(game: &PossibleGames, server_dir: &Path) -> Command {
+    let executable = format!("{}{}", get_game_definition(game).server_executable, std::env::consts::EXE_SUFFIX);
+    let mut command = Command::new(server_dir.join(executable));
+    command.current_dir(server_dir);
+    command
+}
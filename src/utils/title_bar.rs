@@ -0,0 +1,100 @@
+use iced::{
+    mouse::Interaction, widget::{button, column, container, mouse_area, row, Space}, window::Direction, Alignment::Center, Background, Color, Element, Length, Theme
+};
+
+use crate::Message;
+
+use super::icons::{get_icon, Icon};
+
+const CAPTION_BUTTON_WIDTH: f32 = 46.0;
+const RESIZE_BORDER: f32 = 6.0;
+
+fn get_caption_button_style(status: button::Status, hovered: Color) -> button::Style {
+    let background = match status {
+        button::Status::Hovered => Some(hovered),
+        button::Status::Pressed => Some(Color { a: (hovered.a * 1.6).min(1.0), ..hovered }),
+        _ => None,
+    };
+    button::Style {
+        background: background.map(Background::Color),
+        text_color: Color::WHITE,
+        ..button::Style::default()
+    }
+}
+
+fn get_caption_button<'a>(icon: Icon, on_press: Message, hovered: Color, height: f32) -> Element<'a, Message> {
+    button(container(get_icon(icon, 16.0)).center(Length::Fill))
+        .width(Length::Fixed(CAPTION_BUTTON_WIDTH))
+        .height(Length::Fixed(height))
+        .padding(0)
+        .on_press(on_press)
+        .style(move |_: &Theme, status| get_caption_button_style(status, hovered))
+        .into()
+}
+
+/// Title bar for the borderless window: `title` on the left, `actions` before the minimize, maximize and close buttons.
+/// The title and the empty space after it drag the window and double clicking them toggles maximize. The actions sit
+/// outside of that area, a drag started under them would swallow their release on windows and x11.
+pub fn get_title_bar<'a>(title: Element<'a, Message>, actions: Element<'a, Message>, maximized: bool, height: f32) -> Element<'a, Message> {
+    let caption_buttons = row![
+        get_caption_button(Icon::Minimize, Message::Minimize, Color::from_rgba8(255, 255, 255, 0.15), height),
+        get_caption_button(if maximized {Icon::Restore} else {Icon::Maximize}, Message::ToggleMaximize, Color::from_rgba8(255, 255, 255, 0.15), height),
+        get_caption_button(Icon::Close, Message::Close, Color::from_rgba8(232, 17, 35, 0.9), height),
+    ];
+
+    let drag_area = mouse_area(
+        row![
+            container(title).padding([0, 20]),
+            Space::new(Length::Fill, Length::Fill),
+        ]
+        .height(Length::Fill)
+        .align_y(Center)
+    )
+    .on_press(Message::DragStarted)
+    .on_double_click(Message::ToggleMaximize);
+
+    row![
+        drag_area,
+        container(actions).padding([0, 20]),
+        caption_buttons,
+    ]
+    .height(Length::Fixed(height))
+    .align_y(Center)
+    .into()
+}
+
+fn get_resize_handle<'a>(direction: Direction, width: Length, height: Length) -> Element<'a, Message> {
+    let interaction = match direction {
+        Direction::North | Direction::South => Interaction::ResizingVertically,
+        Direction::East | Direction::West => Interaction::ResizingHorizontally,
+        Direction::NorthWest | Direction::SouthEast => Interaction::ResizingDiagonallyDown,
+        Direction::NorthEast | Direction::SouthWest => Interaction::ResizingDiagonallyUp,
+    };
+    mouse_area(Space::new(width, height))
+        .interaction(interaction)
+        .on_press(Message::ResizeStarted(direction))
+        .into()
+}
+
+/// Invisible handles along the window edges for resizing the borderless window, meant as the top layer of a stack.
+pub fn get_resize_handles<'a>() -> Element<'a, Message> {
+    let border = Length::Fixed(RESIZE_BORDER);
+    column![
+        row![
+            get_resize_handle(Direction::NorthWest, border, border),
+            get_resize_handle(Direction::North, Length::Fill, border),
+            get_resize_handle(Direction::NorthEast, border, border),
+        ],
+        row![
+            get_resize_handle(Direction::West, border, Length::Fill),
+            Space::new(Length::Fill, Length::Fill),
+            get_resize_handle(Direction::East, border, Length::Fill),
+        ],
+        row![
+            get_resize_handle(Direction::SouthWest, border, border),
+            get_resize_handle(Direction::South, Length::Fill, border),
+            get_resize_handle(Direction::SouthEast, border, border),
+        ],
+    ]
+    .into()
+}
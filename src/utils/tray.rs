@@ -0,0 +1,190 @@
+use std::{io::Cursor, sync::mpsc, time::Duration};
+
+use ::image::ImageReader;
+use tray_icon::{menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu}, Icon, TrayIcon, TrayIconBuilder, TrayIconEvent};
+
+use crate::{Assets, PossibleGames};
+
+use super::{game_registry::{get_game_by_slug, get_game_definition}, supervisor::ProcessStatus};
+
+/// Everything the tray menu shows, rebuilt from the launcher state whenever it changes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrayMenuState {
+    // every registered game and whether it is running
+    pub games: Vec<(PossibleGames, bool)>,
+    // installed servers and their status, `None` when stopped
+    pub servers: Vec<(PossibleGames, Option<ProcessStatus>)>,
+    pub minimize_on_launch: bool,
+    pub minimize_on_close: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrayAction {
+    Show,
+    Launch(PossibleGames),
+    StartServer(PossibleGames),
+    StopServer(PossibleGames),
+    ToggleMinimizeOnLaunch,
+    ToggleMinimizeOnClose,
+    Quit,
+}
+
+/// Handle to the tray icon, which lives on its own thread running the event loop the platform requires.
+#[derive(Debug)]
+pub struct Tray {
+    updates: mpsc::Sender<TrayMenuState>,
+}
+
+impl Tray {
+    pub fn update(&self, state: TrayMenuState) {
+        let _ = self.updates.send(state);
+    }
+}
+
+fn get_tray_icon() -> Option<Icon> {
+    let file = Assets::get("icon.png")?;
+    let img = ImageReader::new(Cursor::new(file.data)).with_guessed_format().ok()?.decode().ok()?;
+    Icon::from_rgba(img.to_rgba8().into_raw(), img.width(), img.height()).ok()
+}
+
+fn get_tray_menu(state: &TrayMenuState) -> Menu {
+    let menu = Menu::new();
+    let _ = menu.append(&MenuItem::with_id("show", "Show launcher", true, None));
+    let _ = menu.append(&PredefinedMenuItem::separator());
+
+    for (game, running) in &state.games {
+        let definition = get_game_definition(game);
+        let item = if *running {
+            MenuItem::with_id(format!("launch/{}", definition.slug), format!("{} (running)", definition.display_name), false, None)
+        } else {
+            MenuItem::with_id(format!("launch/{}", definition.slug), format!("Launch {}", definition.display_name), true, None)
+        };
+        let _ = menu.append(&item);
+    }
+
+    if !state.servers.is_empty() {
+        let _ = menu.append(&PredefinedMenuItem::separator());
+        for (game, status) in &state.servers {
+            let definition = get_game_definition(game);
+            let (label, action) = match status {
+                None => ("stopped", MenuItem::with_id(format!("server-start/{}", definition.slug), "Start server", true, None)),
+                Some(ProcessStatus::Running) => ("running", MenuItem::with_id(format!("server-stop/{}", definition.slug), "Stop server", true, None)),
+                Some(ProcessStatus::Stopping) => ("stopping", MenuItem::with_id(format!("server-stop/{}", definition.slug), "Stop server", false, None)),
+            };
+            let submenu = Submenu::new(format!("{} server: {label}", definition.display_name), true);
+            let _ = submenu.append(&action);
+            let _ = menu.append(&submenu);
+        }
+    }
+
+    let _ = menu.append(&PredefinedMenuItem::separator());
+    let _ = menu.append(&CheckMenuItem::with_id("minimize-on-launch", "Minimise to tray on launch", true, state.minimize_on_launch, None));
+    let _ = menu.append(&CheckMenuItem::with_id("minimize-on-close", "Minimise to tray on close", true, state.minimize_on_close, None));
+    let _ = menu.append(&PredefinedMenuItem::separator());
+    let _ = menu.append(&MenuItem::with_id("quit", "Quit", true, None));
+    menu
+}
+
+fn get_tray_action(id: &str) -> Option<TrayAction> {
+    match id {
+        "show" => return Some(TrayAction::Show),
+        "quit" => return Some(TrayAction::Quit),
+        "minimize-on-launch" => return Some(TrayAction::ToggleMinimizeOnLaunch),
+        "minimize-on-close" => return Some(TrayAction::ToggleMinimizeOnClose),
+        _ => {}
+    }
+
+    let (action, slug) = id.split_once('/')?;
+    let game = get_game_by_slug(slug)?;
+    match action {
+        "launch" => Some(TrayAction::Launch(game)),
+        "server-start" => Some(TrayAction::StartServer(game)),
+        "server-stop" => Some(TrayAction::StopServer(game)),
+        _ => None,
+    }
+}
+
+/// Drains the clicks on the tray icon and its menu since the last call.
+pub fn get_tray_actions() -> Vec<TrayAction> {
+    let menu_actions = MenuEvent::receiver().try_iter().filter_map(|event| get_tray_action(event.id().as_ref()));
+    let icon_actions = TrayIconEvent::receiver().try_iter().filter_map(|event| match event {
+        TrayIconEvent::DoubleClick { .. } => Some(TrayAction::Show),
+        _ => None,
+    });
+    menu_actions.chain(icon_actions).collect()
+}
+
+fn build_tray(icon: Icon, state: &TrayMenuState) -> Option<TrayIcon> {
+    TrayIconBuilder::new()
+        .with_icon(icon)
+        .with_tooltip(format!("RR Launcher v{}", env!("CARGO_PKG_VERSION")))
+        .with_menu(Box::new(get_tray_menu(state)))
+        .build()
+        .ok()
+}
+
+/// Starts the tray icon, `None` where it isn't supported: macOS wants it on the main thread, which iced owns.
+pub fn spawn_tray(state: TrayMenuState) -> Option<Tray> {
+    if !cfg!(any(target_os = "linux", target_os = "windows")) {
+        return None;
+    }
+
+    let icon = get_tray_icon()?;
+    let (updates, receiver) = mpsc::channel();
+    std::thread::Builder::new()
+        .name("tray".to_string())
+        .spawn(move || run_tray(icon, state, receiver))
+        .ok()?;
+    Some(Tray { updates })
+}
+
+#[cfg(target_os = "linux")]
+fn run_tray(icon: Icon, state: TrayMenuState, receiver: mpsc::Receiver<TrayMenuState>) {
+    if gtk::init().is_err() {
+        return;
+    }
+    let Some(tray) = build_tray(icon, &state) else {
+        return;
+    };
+
+    gtk::glib::timeout_add_local(Duration::from_millis(250), move || {
+        if let Some(state) = receiver.try_iter().last() {
+            tray.set_menu(Some(Box::new(get_tray_menu(&state))));
+        }
+        gtk::glib::ControlFlow::Continue
+    });
+    gtk::main();
+}
+
+#[cfg(target_os = "windows")]
+fn run_tray(icon: Icon, state: TrayMenuState, receiver: mpsc::Receiver<TrayMenuState>) {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{DispatchMessageW, PeekMessageW, TranslateMessage, MSG, PM_REMOVE};
+
+    let Some(tray) = build_tray(icon, &state) else {
+        return;
+    };
+
+    loop {
+        // the tray's hidden window needs its messages pumped on this thread
+        // SAFETY: MSG is plain data, all zeroes is a valid value for PeekMessageW to overwrite. A null HWND asks for
+        // messages of any window owned by the calling thread, and build_tray created the tray's window on this thread,
+        // so every message dispatched here belongs to a window that is alive for the whole loop.
+        unsafe {
+            let mut msg: MSG = std::mem::zeroed();
+            while PeekMessageW(&mut msg, std::ptr::null_mut(), 0, 0, PM_REMOVE) != 0 {
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        }
+
+        match receiver.try_recv() {
+            Ok(state) => tray.set_menu(Some(Box::new(get_tray_menu(&state)))),
+            Err(mpsc::TryRecvError::Disconnected) => break,
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+        std::thread::sleep(Duration::from_millis(16));
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn run_tray(_icon: Icon, _state: TrayMenuState, _receiver: mpsc::Receiver<TrayMenuState>) {}
@@ -1,14 +1,14 @@
 use std::{io::{Cursor, Write}, sync::Arc};
 
 use file_format::FileFormat;
-use iced_video_player::Video;
+use iced_video_player::{RecoveryPolicy, TrackKind, Video};
 use ::image::ImageReader;
 use iced::{widget::{container, image}, Element, Length};
 use tempfile::NamedTempFile;
 
 use crate::{Assets, LauncherBackground, Message, PossibleGames, State};
 
-use super::img_utils::round_image;
+use super::{img_utils::round_image, language::{get_preferred_language, normalize_language}};
 
 pub fn get_game_background(state: &State) -> LauncherBackground {
     let file_path: &str = match state.selected_game {
@@ -25,11 +25,12 @@ pub fn get_game_background(state: &State) -> LauncherBackground {
             let mut temp_file = NamedTempFile::new().unwrap();
             temp_file.write_all(&data).unwrap();
 
-            let temp_path = temp_file.path().to_str().unwrap().to_string();
-            match Video::new(url::Url::from_file_path(temp_path).unwrap()) {
+            let temp_path = temp_file.into_temp_path();
+            match Video::new(url::Url::from_file_path(&temp_path).unwrap()) {
                 Ok(mut video) => {
                     video.set_looping(true);
-                    LauncherBackground::Video(video)
+                    video.set_recovery_policy(RecoveryPolicy::Rebuild { max_attempts: 3 });
+                    LauncherBackground::Video(video, temp_path)
                 },
                 Err(err) => {
                     panic!("{:#?}", err)
@@ -53,6 +54,20 @@ pub fn get_game_background(state: &State) -> LauncherBackground {
     }
 }
 
+pub fn get_placeholder_background() -> LauncherBackground {
+    let file = Assets::get("placeholder.png").unwrap();
+    let img = ImageReader::new(Cursor::new(&*file.data))
+        .with_guessed_format()
+        .unwrap()
+        .decode()
+        .unwrap();
+    LauncherBackground::Image(image::Handle::from_rgba(
+        img.width(),
+        img.height(),
+        img.to_rgba8().into_raw()
+    ))
+}
+
 pub fn get_game_icon_handle(game: &PossibleGames) -> image::Handle {
     let file_path: &str = match game {
         PossibleGames::WutheringWaves => "wutheringwaves-icon.png",
@@ -76,7 +91,26 @@ pub fn get_game_icon_handle(game: &PossibleGames) -> image::Handle {
     }
 }
 
-pub fn get_game_icon<'a>(state: &'a State, game: &'a PossibleGames) -> Element<'a, Message> {
+pub fn get_game_icon<'a>(state: &'a State, game: &'a PossibleGames, size: f32) -> Element<'a, Message> {
     let handle = state.icon_images.get(game).unwrap();
-    container(image(handle).content_fit(iced::ContentFit::Contain).height(Length::Fixed(64.0)).filter_method(image::FilterMethod::Linear)).into()
+    container(image(handle).content_fit(iced::ContentFit::Contain).height(Length::Fixed(size)).filter_method(image::FilterMethod::Linear)).into()
+}
+
+/// Switches the audio and subtitle tracks to the launcher's language, `None` follows the system's.
+pub fn select_preferred_tracks(video: &mut Video, language: Option<&str>) {
+    let Some(language) = get_preferred_language(language) else {
+        return;
+    };
+
+    for kind in [TrackKind::Audio, TrackKind::Subtitle] {
+        // stream tags mix ISO 639-1 and both kinds of 639-2 codes, e.g. "de", "ger" and "deu"
+        let track = video.tracks(kind).into_iter().find(|track| {
+            track.language.as_deref().and_then(normalize_language) == Some(language)
+        });
+        if let Some(track) = track {
+            if video.current_track(kind) != Some(track.index) {
+                let _ = video.set_track(kind, track.index);
+            }
+        }
+    }
 }
\ No newline at end of file
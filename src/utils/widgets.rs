@@ -0,0 +1,66 @@
+use iced::{border, widget::{button, container, text}, Color, Element, Length};
+
+use crate::Message;
+
+/// Secondary action of the panels, greyed out while it has nothing to do.
+pub fn get_small_button<'a>(label: &'a str, on_press: Option<Message>) -> Element<'a, Message> {
+    button(text(label).size(14))
+        .padding([6, 12])
+        .on_press_maybe(on_press)
+        .style(move |_, status| button::Style {
+            text_color: Color::WHITE,
+            background: Some(Color::from_rgba8(255, 255, 255, if status == button::Status::Disabled {0.05} else {0.2}).into()),
+            border: border::rounded(5),
+            ..button::Style::default()
+        })
+        .into()
+}
+
+/// Button of a dialog or form, the primary one is drawn inverted.
+pub fn get_dialog_button<'a>(label: &'a str, on_press: Message, primary: bool) -> Element<'a, Message> {
+    button(text(label).size(15))
+        .padding([8, 16])
+        .on_press(on_press)
+        .style(move |_, _| button::Style {
+            text_color: if primary {Color::BLACK} else {Color::WHITE},
+            background: Some(if primary {Color::WHITE} else {Color::from_rgba8(255, 255, 255, 0.2)}.into()),
+            border: border::rounded(5),
+            ..button::Style::default()
+        })
+        .into()
+}
+
+/// Full window panel drawn over the launcher.
+pub fn get_panel<'a>(content: impl Into<Element<'a, Message>>) -> Element<'a, Message> {
+    container(content)
+        .padding(20)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(move |_| container::Style {
+            text_color: Color::from_rgba8(255, 255, 255, 1.0).into(),
+            background: Some(Color::from_rgba8(10, 10, 10, 0.97).into()),
+            ..container::Style::default()
+        })
+        .into()
+}
+
+/// Centers a dialog over the dimmed launcher.
+pub fn get_modal<'a>(dialog: impl Into<Element<'a, Message>>, max_width: f32) -> Element<'a, Message> {
+    let dialog = container(dialog)
+        .padding(25)
+        .max_width(max_width)
+        .style(move |_| container::Style {
+            text_color: Some(Color::WHITE),
+            background: Some(Color::from_rgba8(20, 20, 20, 0.95).into()),
+            border: border::rounded(8),
+            ..container::Style::default()
+        });
+
+    container(dialog)
+        .center(Length::Fill)
+        .style(move |_| container::Style {
+            background: Some(Color::from_rgba8(0, 0, 0, 0.5).into()),
+            ..container::Style::default()
+        })
+        .into()
+}